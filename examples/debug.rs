@@ -1,8 +1,8 @@
 extern crate lexicon_fractional_index;
 
-use lexicon_fractional_index::key_between;
+use lexicon_fractional_index::{key_between, rebalance, Error};
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), Error> {
   // let next = key_between("Xb0M", "Xb0M0V")?;
 
   // println!("next {}", next);
@@ -22,15 +22,25 @@ fn main() -> Result<(), String> {
 
   let left = "Xb0M".to_owned();
   let mut right = "Xb0M0V".to_owned();
+  let mut keys = vec![left.to_owned(), right.to_owned()];
 
   for _i in 0..1000 {
     let next = key_between(&Some(left.to_owned()), &Some(right.to_owned()))?;
     println!("key: {}", next);
+    keys.push(next.to_owned());
     right = next;
   }
 
   println!("left: {}", left.len());
   println!("left: {}", left);
 
+  keys.sort();
+  let balanced = rebalance(&keys)?;
+  println!("longest: {}", keys.iter().map(String::len).max().unwrap_or(0));
+  println!(
+    "longest after rebalance: {}",
+    balanced.iter().map(String::len).max().unwrap_or(0)
+  );
+
   Ok(())
 }
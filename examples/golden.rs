@@ -0,0 +1,165 @@
+//! generates the golden vectors replayed by tests/golden_test.rs:
+//!
+//! `cargo run --example golden > tests/golden/key_between.jsonl`
+//!
+//! Inputs are deterministic, outputs are those of this crate. CI replays them against
+//! the JavaScript and Go packages with `tests/golden/check.mjs` and `check.go`.
+
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{key_between, n_keys_between, Error};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::json;
+
+const SMALLEST: &str = "A00000000000000000000000000";
+const LARGEST: &str = "zzzzzzzzzzzzzzzzzzzzzzzzzzz";
+
+/// counts of keys to ask n_keys_between for
+const COUNTS: [usize; 9] = [0, 1, 2, 3, 4, 5, 7, 10, 16];
+
+fn main() -> Result<(), Error> {
+  let mut rng = StdRng::seed_from_u64(20);
+  let pool = pool(&mut rng)?;
+  let mut cases = vec![];
+
+  // open ends
+  for key in pool.iter() {
+    cases.push((None, Some(key.to_owned()), None));
+    cases.push((Some(key.to_owned()), None, None));
+  }
+  // neighbours, then arbitrary pairs
+  for pair in pool.windows(2) {
+    cases.push((Some(pair[0].to_owned()), Some(pair[1].to_owned()), None));
+  }
+  for _ in 0..1000 {
+    let (a, b) = pair(&mut rng, &pool);
+    cases.push((a, b, None));
+  }
+  for _ in 0..1000 {
+    let (a, b) = pair(&mut rng, &pool);
+    let n = COUNTS[rng.gen_range(0..COUNTS.len())];
+    cases.push((a, b, Some(n)));
+  }
+
+  // errors. n == 0 is left out: the JavaScript and Go versions return no keys
+  // without validating the bounds, this crate validates them.
+  let invalid = [
+    "", "0", "é", "a", "b1", "c00", "A0", SMALLEST, "a00", "a0V0", "b1230", "Zz0",
+  ];
+  for key in invalid {
+    for n in [None, Some(1), Some(2)] {
+      cases.push((Some(key.to_owned()), None, n));
+      cases.push((None, Some(key.to_owned()), n));
+      cases.push((Some(key.to_owned()), Some("a1".to_owned()), n));
+      cases.push((Some("Zz".to_owned()), Some(key.to_owned()), n));
+    }
+  }
+  for _ in 0..200 {
+    let (a, b) = pair(&mut rng, &pool);
+    let (a, b) = (
+      a.unwrap_or_else(|| "a0".to_owned()),
+      b.unwrap_or_else(|| "a0".to_owned()),
+    );
+    let n = [None, Some(1), Some(3)][rng.gen_range(0..3)];
+    // reversed and equal bounds
+    cases.push((Some(b.to_owned()), Some(a.to_owned()), n));
+    cases.push((Some(a.to_owned()), Some(a), n));
+  }
+
+  for (a, b, n) in cases {
+    let result = match n {
+      None => key_between(&a, &b).map(|key| vec![key]),
+      Some(n) => n_keys_between(&a, &b, n),
+    };
+    let case = match result {
+      Ok(keys) => json!({ "a": a, "b": b, "n": n, "keys": keys }),
+      Err(e) => json!({ "a": a, "b": b, "n": n, "error": e.to_string() }),
+    };
+    println!("{}", case);
+  }
+
+  Ok(())
+}
+
+/// valid keys: edges of the integer range and of heads, keys of the upstream tests,
+/// and keys of random insertions. Sorted, without duplicates.
+fn pool(rng: &mut StdRng) -> Result<Vec<String>, Error> {
+  let mut pool: Vec<String> = [
+    "a0",
+    "a1",
+    "a2",
+    "a0V",
+    "a0G",
+    "a08",
+    "a1V",
+    "a0l",
+    "Zz",
+    "Zy",
+    "ZzV",
+    "Z0",
+    "Yzz",
+    "Y00",
+    "Xzzz",
+    "az",
+    "b00",
+    "bzz",
+    "c000",
+    "b125",
+    "b127",
+    "b129",
+    "a01",
+    "b99",
+    "b999",
+    "A00000000000000000000000001",
+    "A000000000000000000000000001",
+    "Azzzzzzzzzzzzzzzzzzzzzzzzzz",
+    "B0000000000000000000000000",
+    "zzzzzzzzzzzzzzzzzzzzzzzzzzy",
+    LARGEST,
+    "zzzzzzzzzzzzzzzzzzzzzzzzzzzV",
+    "yzzzzzzzzzzzzzzzzzzzzzzzzz",
+  ]
+  .iter()
+  .map(|key| key.to_string())
+  .collect();
+
+  // appending and prepending walk through the integers
+  let (mut last, mut first) = (Some("a0".to_owned()), Some("a0".to_owned()));
+  for i in 0..300 {
+    last = Some(key_between(&last, &None)?);
+    first = Some(key_between(&None, &first)?);
+    if i % 7 == 0 {
+      pool.extend(last.iter().chain(first.iter()).cloned());
+    }
+  }
+
+  // random insertions grow fractional parts
+  let mut keys: Vec<String> = vec![];
+  for _ in 0..300 {
+    let i = rng.gen_range(0..=keys.len());
+    let a = i.checked_sub(1).map(|i| keys[i].to_owned());
+    let b = keys.get(i).cloned();
+    keys.insert(i, key_between(&a, &b)?);
+  }
+  pool.extend(keys.into_iter().step_by(3));
+  // repeated insertions next to the same key, with long keys
+  let mut right = Some("a1".to_owned());
+  for _ in 0..40 {
+    right = Some(key_between(&Some("a0".to_owned()), &right)?);
+    pool.extend(right.iter().cloned());
+  }
+
+  pool.sort();
+  pool.dedup();
+  Ok(pool)
+}
+
+/// a < b, either one None a tenth of the time
+fn pair(rng: &mut StdRng, pool: &[String]) -> (Option<String>, Option<String>) {
+  let i = rng.gen_range(0..pool.len() - 1);
+  let j = rng.gen_range(i + 1..pool.len());
+  let a = Some(pool[i].to_owned()).filter(|_| rng.gen_ratio(9, 10));
+  let b = Some(pool[j].to_owned()).filter(|_| rng.gen_ratio(9, 10));
+  (a, b)
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+use lexicon_fractional_index::{float64_approx, Alphabet};
+use libfuzzer_sys::fuzz_target;
+
+// float64_approx keeps the order of valid keys sharing a positive head
+fuzz_target!(|keys: (&str, &str)| {
+  let (a, b) = if keys.0 <= keys.1 { keys } else { (keys.1, keys.0) };
+  let valid = Alphabet::BASE62.validate(a).is_ok() && Alphabet::BASE62.validate(b).is_ok();
+  if !valid || a.as_bytes()[0] != b.as_bytes()[0] || a.as_bytes()[0] < b'a' {
+    return;
+  }
+  let (x, y) = (float64_approx(a).unwrap(), float64_approx(b).unwrap());
+  assert!(x <= y, "{:?} is {} but {:?} is {}", a, x, b, y);
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+use lexicon_fractional_index::{key_between, Alphabet, Error};
+use libfuzzer_sys::fuzz_target;
+
+// for valid keys a < b, the key between is valid and strictly between them
+fuzz_target!(|bounds: (&str, &str, bool, bool)| {
+  let (a, b, open_a, open_b) = bounds;
+  let valid = Alphabet::BASE62.validate(a).is_ok() && Alphabet::BASE62.validate(b).is_ok() && a < b;
+  let a = Some(a.to_owned()).filter(|_| !open_a);
+  let b = Some(b.to_owned()).filter(|_| !open_b);
+
+  // malformed bounds are an error, never a panic
+  let result = key_between(&a, &b);
+  if !valid {
+    return;
+  }
+
+  let key = result.unwrap();
+  // before the integer after it, the reserved smallest integer is returned, as the JS
+  // and Go implementations do
+  let smallest = a.is_none() && matches!(Alphabet::BASE62.validate(&key), Err(Error::SmallestKey { .. }));
+  assert!(
+    smallest || Alphabet::BASE62.validate(&key).is_ok(),
+    "{:?} is not a valid key",
+    key
+  );
+  assert!(a.is_none_or(|a| a < key), "{:?} is not after the lower bound", key);
+  assert!(b.is_none_or(|b| key < b), "{:?} is not before the upper bound", key);
+});
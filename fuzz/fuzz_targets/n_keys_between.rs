@@ -0,0 +1,38 @@
+#![no_main]
+
+use lexicon_fractional_index::{n_keys_between, Alphabet, Error};
+use libfuzzer_sys::fuzz_target;
+
+// for valid keys a < b, n_keys_between returns n valid keys, strictly increasing between them
+fuzz_target!(|bounds: (&str, &str, bool, bool, u8)| {
+  let (a, b, open_a, open_b, n) = bounds;
+  let valid = Alphabet::BASE62.validate(a).is_ok() && Alphabet::BASE62.validate(b).is_ok() && a < b;
+  let a = Some(a.to_owned()).filter(|_| !open_a);
+  let b = Some(b.to_owned()).filter(|_| !open_b);
+
+  // malformed bounds are an error, never a panic
+  let result = n_keys_between(&a, &b, n as usize);
+  if !valid {
+    return;
+  }
+
+  let keys = result.unwrap();
+  assert_eq!(keys.len(), n as usize);
+  for key in &keys {
+    // with an open lower bound, a key may be the reserved smallest integer,
+    // as in the JS and Go implementations
+    let smallest = a.is_none() && matches!(Alphabet::BASE62.validate(key), Err(Error::SmallestKey { .. }));
+    assert!(
+      smallest || Alphabet::BASE62.validate(key).is_ok(),
+      "{:?} is not a valid key",
+      key
+    );
+  }
+  let mut bounded = a.iter().chain(&keys).chain(&b);
+  if let Some(mut prev) = bounded.next() {
+    for key in bounded {
+      assert!(prev < key, "{:?} is not before {:?}", prev, key);
+      prev = key;
+    }
+  }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use lexicon_fractional_index::{float64_approx, Alphabet, FractionalIndex};
+use libfuzzer_sys::fuzz_target;
+
+// validation never panics, whatever the input
+fuzz_target!(|key: &str| {
+  if FractionalIndex::parse(key).is_ok() {
+    assert!(!key.is_empty());
+  }
+  assert_eq!(
+    Alphabet::BASE62.validate(key).is_ok(),
+    FractionalIndex::parse(key).is_ok()
+  );
+  let _ = float64_approx(key);
+});
@@ -0,0 +1,371 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::error::key_string;
+#[cfg(feature = "std")]
+use crate::float64_approx_valid;
+#[cfg(feature = "alloc")]
+use crate::{into_string, key_between_valid, n_keys_between_valid};
+use crate::{validate_bounds, validate_order_key, write_key_between, Error, KeyBuf, StackKey};
+
+/// Alphabet is the set of characters order keys are written with.
+///
+/// `digits` are the digits of the integer and fractional parts, in ascending order.
+/// `heads` are the characters an integer part starts with, in ascending order:
+/// the first half mark negative integers (longest first), the second half
+/// positive integers (shortest first). With base62 these are `A-Z` and `a-z`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alphabet {
+  /// digits, followed by unused zero bytes
+  digits: [u8; 256],
+  /// heads, followed by unused zero bytes
+  heads: [u8; 256],
+  radix: u16,
+  head_count: u16,
+  /// value of every byte as a digit, `NONE` when it is not one
+  digit_values: [u16; 256],
+  /// index of every byte in heads, `NONE` when it is not one
+  head_indexes: [u16; 256],
+}
+
+/// marks bytes missing from a lookup table, above any index of 256 bytes
+const NONE: u16 = u16::MAX;
+
+/// bytes copied to the start of a zeroed table
+const fn byte_table(bytes: &[u8]) -> [u8; 256] {
+  let mut table = [0; 256];
+  let mut i = 0;
+  while i < bytes.len() {
+    table[i] = bytes[i];
+    i += 1;
+  }
+  table
+}
+
+/// lookup table from byte to its index in bytes
+const fn lookup_table(bytes: &[u8]) -> [u16; 256] {
+  let mut table = [NONE; 256];
+  let mut i = 0;
+  while i < bytes.len() {
+    table[bytes[i] as usize] = i as u16;
+    i += 1;
+  }
+  table
+}
+
+#[cfg(feature = "alloc")]
+const ALL_BYTES: [u8; 256] = {
+  let mut bytes = [0; 256];
+  let mut i = 0;
+  while i < bytes.len() {
+    bytes[i] = i as u8;
+    i += 1;
+  }
+  bytes
+};
+
+impl Alphabet {
+  /// `0-9A-Za-z` digits with `A-Z`/`a-z` heads, the default of this crate.
+  pub const BASE62: Alphabet = Alphabet::from_bytes(
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+  );
+
+  /// every printable ASCII character (` ` to `~`) as digits, with base62 heads.
+  /// Keys are denser, but contain characters like space, quotes and backslash.
+  pub const BASE95: Alphabet = Alphabet::from_bytes(
+    b" !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~",
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+  );
+
+  /// `0-9a-z` for both digits and heads, so keys sort the same under
+  /// case-insensitive collations. `0-h` are negative heads, `i-z` positive ones.
+  pub const BASE36: Alphabet = Alphabet::from_bytes(
+    b"0123456789abcdefghijklmnopqrstuvwxyz",
+    b"0123456789abcdefghijklmnopqrstuvwxyz",
+  );
+
+  /// `0-9a-f` for both digits and heads, keys are plain lowercase hex strings.
+  /// `0-7` are negative heads, `8-f` positive ones.
+  pub const HEX: Alphabet = Alphabet::from_bytes(b"0123456789abcdef", b"0123456789abcdef");
+
+  /// every byte as digit and head, for keys compared as raw bytes. Not ASCII,
+  /// so it is only used through the `binary` module.
+  #[cfg(feature = "alloc")]
+  pub(crate) const BINARY: Alphabet = Alphabet::from_bytes(&ALL_BYTES, &ALL_BYTES);
+
+  /// new returns a user defined alphabet.
+  /// Both `digits` and `heads` must be ASCII and strictly ascending,
+  /// `digits` needs at least 2 characters and `heads` an even, non-zero count.
+  /// Both are copied, so they can come from runtime strings.
+  pub fn new(digits: &str, heads: &str) -> Result<Alphabet, Error> {
+    check_ascending(digits)?;
+    check_ascending(heads)?;
+    if digits.len() < 2 {
+      return Err(Error::InvalidAlphabet {
+        chars: key_string(digits.as_bytes()),
+        position: digits.len(),
+      });
+    }
+    if heads.is_empty() || !heads.len().is_multiple_of(2) {
+      return Err(Error::InvalidAlphabet {
+        chars: key_string(heads.as_bytes()),
+        position: heads.len(),
+      });
+    }
+    Ok(Alphabet::from_bytes(digits.as_bytes(), heads.as_bytes()))
+  }
+
+  /// digits and heads MUST be strictly ascending, so at most 256 bytes each
+  const fn from_bytes(digits: &[u8], heads: &[u8]) -> Alphabet {
+    Alphabet {
+      digits: byte_table(digits),
+      heads: byte_table(heads),
+      radix: digits.len() as u16,
+      head_count: heads.len() as u16,
+      digit_values: lookup_table(digits),
+      head_indexes: lookup_table(heads),
+    }
+  }
+
+  pub fn digits(&self) -> &str {
+    core::str::from_utf8(self.digit_bytes()).expect("alphabet is ASCII")
+  }
+
+  pub fn heads(&self) -> &str {
+    core::str::from_utf8(self.head_bytes()).expect("alphabet is ASCII")
+  }
+
+  fn digit_bytes(&self) -> &[u8] {
+    &self.digits[..self.radix()]
+  }
+
+  fn head_bytes(&self) -> &[u8] {
+    &self.heads[..self.head_count as usize]
+  }
+
+  /// validate checks that key is a valid key of this alphabet, as
+  /// `FractionalIndex::parse` does for base62 keys.
+  pub fn validate(&self, key: &str) -> Result<(), Error> {
+    validate_order_key(self, key.as_bytes())
+  }
+
+  /// key_between returns a key that sorts lexicographically between a and b.
+  /// Either a or b can be empty strings. If a is empty it indicates smallest key,
+  /// If b is empty it indicates largest key.
+  /// b must be empty string or > a.
+  #[cfg(feature = "alloc")]
+  pub fn key_between(&self, a: &Option<String>, b: &Option<String>) -> Result<String, Error> {
+    let (a, b) = (a.as_deref().map(str::as_bytes), b.as_deref().map(str::as_bytes));
+    validate_bounds(self, a, b)?;
+    key_between_valid(self, a, b).map(into_string)
+  }
+
+  /// key_between_into appends a key that sorts lexicographically between a and b to out.
+  /// Nothing is allocated besides what out needs to grow, so reusing a `String` or
+  /// writing into a `StackKey` generates keys without allocations.
+  /// On error out is left unchanged.
+  /// Either a or b can be None. If a is None it indicates smallest key,
+  /// If b is None it indicates largest key.
+  /// b must be None or > a.
+  pub fn key_between_into<B: KeyBuf + ?Sized>(
+    &self,
+    a: Option<&str>,
+    b: Option<&str>,
+    out: &mut B,
+  ) -> Result<(), Error> {
+    let (a, b) = (a.map(str::as_bytes), b.map(str::as_bytes));
+    validate_bounds(self, a, b)?;
+    write_key_between(self, a, b, out)
+  }
+
+  /// key_between_stack returns a key that sorts lexicographically between a and b
+  /// as a `StackKey`, or `Error::BufferFull` if it is longer than N bytes.
+  /// It works without the `alloc` feature, as `key_between_into` does.
+  pub fn key_between_stack<const N: usize>(&self, a: Option<&str>, b: Option<&str>) -> Result<StackKey<N>, Error> {
+    let mut out = StackKey::new();
+    self.key_between_into(a, b, &mut out)?;
+    Ok(out)
+  }
+
+  /// n_keys_between returns n keys between a and b that sorts lexicographically.
+  /// Either a or b can be empty strings. If a is empty it indicates smallest key,
+  /// If b is empty it indicates largest key.
+  /// b must be empty string or > a.
+  #[cfg(feature = "alloc")]
+  pub fn n_keys_between(&self, a: &Option<String>, b: &Option<String>, n: usize) -> Result<Vec<String>, Error> {
+    let (a, b) = (a.as_deref().map(str::as_bytes), b.as_deref().map(str::as_bytes));
+    validate_bounds(self, a, b)?;
+    let keys = n_keys_between_valid(self, a, b, n)?;
+    Ok(keys.into_iter().map(into_string).collect())
+  }
+
+  /// key_between_unchecked is `key_between` without validating a and b, for keys that
+  /// were validated before, e.g. when they were stored.
+  /// a and b MUST be valid keys and a < b, otherwise the result is unspecified: an error,
+  /// a key out of order or invalid, or a panic. Debug builds assert it.
+  #[cfg(feature = "alloc")]
+  pub fn key_between_unchecked(&self, a: Option<&str>, b: Option<&str>) -> Result<String, Error> {
+    let (a, b) = (a.map(str::as_bytes), b.map(str::as_bytes));
+    debug_assert_eq!(validate_bounds(self, a, b), Ok(()), "unchecked bounds");
+    key_between_valid(self, a, b).map(into_string)
+  }
+
+  /// n_keys_between_unchecked is `n_keys_between` without validating a and b.
+  /// a and b MUST be valid keys and a < b, see `key_between_unchecked`.
+  #[cfg(feature = "alloc")]
+  pub fn n_keys_between_unchecked(&self, a: Option<&str>, b: Option<&str>, n: usize) -> Result<Vec<String>, Error> {
+    let (a, b) = (a.map(str::as_bytes), b.map(str::as_bytes));
+    debug_assert_eq!(validate_bounds(self, a, b), Ok(()), "unchecked bounds");
+    let keys = n_keys_between_valid(self, a, b, n)?;
+    Ok(keys.into_iter().map(into_string).collect())
+  }
+
+  /// float64_approx converts a key as generated by key_between() to a float64.
+  /// Because the range of keys is far larger than float64 can represent
+  /// accurately, this is necessarily approximate. But for many use cases it should
+  /// be, as they say, close enough for jazz.
+  ///
+  /// As in the Go implementation, it doesn't keep the order of keys across heads:
+  /// `az` is 61 but `b00` is 0, `Z0` is -0 but `Zz` is -61. Only keys sharing a
+  /// positive head convert in order.
+  #[cfg(feature = "std")]
+  pub fn float64_approx(&self, key: &str) -> Result<f64, Error> {
+    validate_order_key(self, key.as_bytes())?;
+
+    float64_approx_valid(self, key.as_bytes())
+  }
+
+  /// number of digits
+  pub(crate) fn radix(&self) -> usize {
+    self.radix as usize
+  }
+
+  pub(crate) fn digit_value(&self, c: u8) -> Option<usize> {
+    match self.digit_values[c as usize] {
+      NONE => None,
+      value => Some(value as usize),
+    }
+  }
+
+  pub(crate) fn digit(&self, value: usize) -> u8 {
+    self.digits[value]
+  }
+
+  pub(crate) fn zero_digit(&self) -> u8 {
+    self.digit(0)
+  }
+
+  pub(crate) fn max_digit(&self) -> u8 {
+    self.digit(self.radix() - 1)
+  }
+
+  pub(crate) fn head(&self, index: usize) -> u8 {
+    self.heads[index]
+  }
+
+  /// number of negative (and of positive) heads
+  pub(crate) fn half(&self) -> usize {
+    self.head_count as usize / 2
+  }
+
+  pub(crate) fn head_index(&self, head: u8) -> Option<usize> {
+    match self.head_indexes[head as usize] {
+      NONE => None,
+      index => Some(index as usize),
+    }
+  }
+
+  #[cfg(feature = "alloc")]
+  pub(crate) fn is_negative_head(&self, head: u8) -> bool {
+    self.head_index(head).is_some_and(|i| i < self.half())
+  }
+
+  /**
+   * length map, with n heads per sign:
+   * negative heads -> n+1 ... 2
+   * positive heads -> 2 ... n+1
+   */
+  pub(crate) fn int_len(&self, head: u8) -> Option<usize> {
+    let i = self.head_index(head)?;
+    if i < self.half() {
+      Some(self.half() - i + 1)
+    } else {
+      Some(i - self.half() + 2)
+    }
+  }
+
+  /// the key of integer 0, e.g. `a0` for base62
+  #[cfg(feature = "alloc")]
+  pub(crate) fn zero(&self) -> Vec<u8> {
+    vec![self.head(self.half()), self.zero_digit()]
+  }
+
+  /// whether int is the smallest integer, e.g. `A00000000000000000000000000` for base62
+  pub(crate) fn is_smallest_int(&self, int: &[u8]) -> bool {
+    int.len() == self.half() + 1 && int[0] == self.head(0) && int[1..].iter().all(|d| *d == self.zero_digit())
+  }
+
+  /// transcode rewrites a valid key of this alphabet into `to`, digit by digit,
+  /// keeping the length of the integer part. Order between keys is preserved.
+  /// Fails when a head or digit has no counterpart in `to`.
+  #[cfg(feature = "alloc")]
+  pub(crate) fn transcode(&self, key: &[u8], to: &Alphabet) -> Result<Vec<u8>, Error> {
+    let int_len = self.int_len(key[0]).unwrap();
+    let head = if self.is_negative_head(key[0]) {
+      to.half().checked_sub(int_len - 1)
+    } else {
+      Some(to.half() + int_len - 2).filter(|h| *h < to.head_count as usize)
+    };
+    let mut out = match head {
+      Some(head) => vec![to.head(head)],
+      None => return Err(Error::InvalidHead { key: key_string(key) }),
+    };
+    for (position, c) in key.iter().enumerate().skip(1) {
+      match self.digit_value(*c).filter(|d| *d < to.radix()) {
+        Some(d) => out.push(to.digit(d)),
+        None => {
+          return Err(Error::InvalidDigit {
+            key: key_string(key),
+            position,
+          })
+        }
+      }
+    }
+    validate_order_key(to, &out)?;
+    Ok(out)
+  }
+}
+
+impl fmt::Debug for Alphabet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Alphabet")
+      .field("digits", &self.digit_bytes())
+      .field("heads", &self.head_bytes())
+      .finish()
+  }
+}
+
+impl Default for Alphabet {
+  fn default() -> Self {
+    Alphabet::BASE62
+  }
+}
+
+fn check_ascending(chars: &str) -> Result<(), Error> {
+  let bytes = chars.as_bytes();
+  for (i, c) in bytes.iter().enumerate() {
+    if !c.is_ascii() || (i > 0 && bytes[i - 1] >= *c) {
+      return Err(Error::InvalidAlphabet {
+        chars: key_string(chars.as_bytes()),
+        position: i,
+      });
+    }
+  }
+  Ok(())
+}
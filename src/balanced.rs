@@ -0,0 +1,280 @@
+//! Evenly spaced keys, computed with arbitrary-precision arithmetic.
+//!
+//! Every key is read as a number: the rank of its integer part among all integer
+//! parts, followed by the digits of its fractional part. With `l` fractional digits
+//! all keys are integers in units of `radix^-l`, written as fixed width, big-endian
+//! digit vectors, so bounds can be subtracted and the difference divided evenly.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{get_int_part, into_string, validate_bounds, Alphabet, Error};
+
+impl Alphabet {
+  /// n_keys_between_balanced returns n keys between a and b that sorts lexicographically,
+  /// evenly spaced over the interval. All keys have at most as many fractional digits
+  /// as needed to fit n distinct keys, so when integers alone fit, keys have none.
+  /// An open bound is replaced by the edge of the integer length of the other bound,
+  /// e.g. keys after `a5` are spread over `a6` to `az`, or over their fractions.
+  /// Either a or b can be empty strings. If a is empty it indicates smallest key,
+  /// If b is empty it indicates largest key.
+  /// b must be empty string or > a.
+  pub fn n_keys_between_balanced(
+    &self,
+    a: &Option<String>,
+    b: &Option<String>,
+    n: usize,
+  ) -> Result<Vec<String>, Error> {
+    let (a, b) = (a.as_deref().map(str::as_bytes), b.as_deref().map(str::as_bytes));
+    validate_bounds(self, a, b)?;
+    let keys = n_keys_balanced_valid(self, a, b, n)?;
+    Ok(keys.into_iter().map(into_string).collect())
+  }
+}
+
+/// same as n_keys_between_balanced, but a and b MUST already be validated and ordered.
+pub(crate) fn n_keys_balanced_valid(
+  alphabet: &Alphabet,
+  a: Option<&[u8]>,
+  b: Option<&[u8]>,
+  n: usize,
+) -> Result<Vec<Vec<u8>>, Error> {
+  if n == 0 {
+    return Ok(vec![]);
+  }
+  let ranks = Ranks::new(alphabet);
+  let lower = match a {
+    Some(a) => ranks.bound(a)?,
+    None => ranks.lower_edge(b)?,
+  };
+  let upper = match b {
+    Some(b) => ranks.bound(b)?,
+    None => ranks.upper_edge(a)?,
+  };
+
+  // fewest fractional digits giving more than n steps between the bounds
+  let mut len = 0;
+  let (lo, span) = loop {
+    let lo = lower.at(len, false);
+    let span = sub(&upper.at(len, true), &lo, alphabet.radix());
+    if span >= ranks.number(n as u128 + 1, len) {
+      break (lo, span);
+    }
+    len += 1;
+  };
+
+  // key i is at lo + i * span / (n + 1), rounded down
+  let (step, rem) = div_small(&span, n as u128 + 1, alphabet.radix());
+  let mut keys = Vec::with_capacity(n);
+  let mut base = lo;
+  for i in 1..=n as u128 {
+    add(&mut base, &step, alphabet.radix());
+    let mut value = base.clone();
+    add_small(&mut value, i * rem / (n as u128 + 1), alphabet.radix());
+    keys.push(ranks.key(&value, len));
+  }
+  Ok(keys)
+}
+
+/// a key as a number: rank of its integer part, then its fractional digits
+struct Bound {
+  rank: Vec<usize>,
+  fraction: Vec<usize>,
+  radix: usize,
+}
+
+impl Bound {
+  /// the bound in units of `radix^-len`, rounded down, or up if `ceil`
+  fn at(&self, len: usize, ceil: bool) -> Vec<usize> {
+    let mut value = self.rank.clone();
+    value.extend(self.fraction.iter().copied().chain(core::iter::repeat(0)).take(len));
+    if ceil && self.fraction.len() > len {
+      add_small(&mut value, 1, self.radix);
+    }
+    value
+  }
+}
+
+/// ranks of the first integer of every head, so `offsets[h]` ranks the integers
+/// of head h from `offsets[h]` on. The smallest integer has rank 0.
+struct Ranks<'a> {
+  alphabet: &'a Alphabet,
+  offsets: Vec<Vec<usize>>,
+}
+
+impl<'a> Ranks<'a> {
+  fn new(alphabet: &'a Alphabet) -> Self {
+    // there are less than `radix^(half + 2)` integers, one more digit leaves room for sums
+    let width = alphabet.half() + 3;
+    let heads = alphabet.half() * 2;
+    let mut offsets = Vec::with_capacity(heads + 1);
+    let mut offset = vec![0; width];
+    offsets.push(offset.clone());
+    for h in 0..heads {
+      // head h has `radix^int_digits` integers
+      let mut size = vec![0; width];
+      size[width - 1 - int_digits(alphabet, h)] = 1;
+      add(&mut offset, &size, alphabet.radix());
+      offsets.push(offset.clone());
+    }
+    Ranks { alphabet, offsets }
+  }
+
+  fn width(&self) -> usize {
+    self.offsets[0].len()
+  }
+
+  /// a fixed width number of the rank width followed by len zero digits
+  fn number(&self, value: u128, len: usize) -> Vec<usize> {
+    let mut number = vec![0; self.width() + len];
+    add_small(&mut number, value, self.alphabet.radix());
+    number
+  }
+
+  fn bound(&self, key: &[u8]) -> Result<Bound, Error> {
+    let int_part = get_int_part(self.alphabet, key)?;
+    let head = self.alphabet.head_index(int_part[0]).unwrap();
+    let mut rank = self.offsets[head].clone();
+    let mut digits = vec![0; self.width()];
+    let start = self.width() - (int_part.len() - 1);
+    for (d, c) in digits[start..].iter_mut().zip(&int_part[1..]) {
+      *d = self.alphabet.digit_value(*c).unwrap();
+    }
+    add(&mut rank, &digits, self.alphabet.radix());
+    Ok(Bound {
+      rank,
+      fraction: key[int_part.len()..]
+        .iter()
+        .map(|c| self.alphabet.digit_value(*c).unwrap())
+        .collect(),
+      radix: self.alphabet.radix(),
+    })
+  }
+
+  /// exclusive lower bound replacing an open a: the last integer before the
+  /// head of b, or before the head below when b is the first integer of its head
+  fn lower_edge(&self, b: Option<&[u8]>) -> Result<Bound, Error> {
+    let mut head = self.alphabet.half();
+    if let Some(b) = b {
+      let int_part = get_int_part(self.alphabet, b)?;
+      head = self.alphabet.head_index(int_part[0]).unwrap();
+      let first_of_head = int_part[1..].iter().all(|d| *d == self.alphabet.zero_digit());
+      if first_of_head && int_part.len() == b.len() {
+        head -= 1;
+      }
+    }
+    let mut rank = self.offsets[head].clone();
+    // below the first head the smallest integer is the bound, it is no valid key itself
+    if head > 0 {
+      sub_one(&mut rank, self.alphabet.radix());
+    }
+    Ok(self.edge(rank))
+  }
+
+  /// exclusive upper bound replacing an open b: the first integer after the head of a
+  fn upper_edge(&self, a: Option<&[u8]>) -> Result<Bound, Error> {
+    let head = match a {
+      Some(a) => self.alphabet.head_index(get_int_part(self.alphabet, a)?[0]).unwrap(),
+      None => self.alphabet.half(),
+    };
+    Ok(self.edge(self.offsets[head + 1].clone()))
+  }
+
+  fn edge(&self, rank: Vec<usize>) -> Bound {
+    Bound {
+      rank,
+      fraction: vec![],
+      radix: self.alphabet.radix(),
+    }
+  }
+
+  /// the key of value, a number with len fractional digits
+  fn key(&self, value: &[usize], len: usize) -> Vec<u8> {
+    let (rank, fraction) = value.split_at(value.len() - len);
+    let head = self
+      .offsets
+      .iter()
+      .rposition(|offset| offset.as_slice() <= rank)
+      .unwrap();
+    let digits = sub(rank, &self.offsets[head], self.alphabet.radix());
+
+    let mut key = vec![self.alphabet.head(head)];
+    let int_digits = int_digits(self.alphabet, head);
+    key.extend(
+      digits[digits.len() - int_digits..]
+        .iter()
+        .map(|d| self.alphabet.digit(*d)),
+    );
+    let fraction_len = fraction.iter().rposition(|d| *d != 0).map_or(0, |i| i + 1);
+    key.extend(fraction[..fraction_len].iter().map(|d| self.alphabet.digit(*d)));
+    key
+  }
+}
+
+/// number of digits after head h
+fn int_digits(alphabet: &Alphabet, h: usize) -> usize {
+  alphabet.int_len(alphabet.head(h)).unwrap() - 1
+}
+
+/// x += y, both of the same width
+fn add(x: &mut [usize], y: &[usize], radix: usize) {
+  let mut carry = 0;
+  for (d, e) in x.iter_mut().zip(y).rev() {
+    let sum = *d + e + carry;
+    *d = sum % radix;
+    carry = sum / radix;
+  }
+}
+
+/// x += value
+fn add_small(x: &mut [usize], mut value: u128, radix: usize) {
+  for d in x.iter_mut().rev() {
+    if value == 0 {
+      break;
+    }
+    let sum = *d as u128 + value;
+    *d = (sum % radix as u128) as usize;
+    value = sum / radix as u128;
+  }
+}
+
+/// x -= 1, x MUST be positive
+fn sub_one(x: &mut [usize], radix: usize) {
+  for d in x.iter_mut().rev() {
+    if *d > 0 {
+      *d -= 1;
+      return;
+    }
+    *d = radix - 1;
+  }
+}
+
+/// x - y, both of the same width and x >= y
+fn sub(x: &[usize], y: &[usize], radix: usize) -> Vec<usize> {
+  let mut result = vec![0; x.len()];
+  let mut borrow = 0;
+  for i in (0..x.len()).rev() {
+    let e = y[i] + borrow;
+    if x[i] >= e {
+      result[i] = x[i] - e;
+      borrow = 0;
+    } else {
+      result[i] = x[i] + radix - e;
+      borrow = 1;
+    }
+  }
+  result
+}
+
+/// x / divisor and x % divisor
+fn div_small(x: &[usize], divisor: u128, radix: usize) -> (Vec<usize>, u128) {
+  let mut quotient = Vec::with_capacity(x.len());
+  let mut rem: u128 = 0;
+  for d in x {
+    let cur = rem * radix as u128 + *d as u128;
+    quotient.push((cur / divisor) as usize);
+    rem = cur % divisor;
+  }
+  (quotient, rem)
+}
@@ -0,0 +1,62 @@
+//! Binary keys, for storage engines that compare raw bytes (RocksDB, sled, ...).
+//!
+//! Keys have the same layout as the string keys: a head byte giving the length
+//! of the integer part, then the fractional part. Every byte is a digit, heads
+//! `0x00-0x7f` are negative integers and `0x80-0xff` positive ones, so `[0x80, 0x00]`
+//! is the first key. The fractional part must not end with `0x00`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+  into_string, key_between_valid, n_keys_between_valid, validate_bounds, validate_order_key, write_key_between,
+  Alphabet, Error,
+};
+
+/// key_between returns a key that sorts bytewise between a and b.
+/// Either a or b can be empty. If a is empty it indicates smallest key,
+/// If b is empty it indicates largest key.
+/// b must be empty or > a.
+pub fn key_between(a: &Option<Vec<u8>>, b: &Option<Vec<u8>>) -> Result<Vec<u8>, Error> {
+  let (a, b) = (a.as_deref(), b.as_deref());
+  validate_bounds(&Alphabet::BINARY, a, b)?;
+  key_between_valid(&Alphabet::BINARY, a, b)
+}
+
+/// key_between_into appends a key that sorts bytewise between a and b to out,
+/// without allocating when out has room for it. On error out is left unchanged.
+pub fn key_between_into(a: Option<&[u8]>, b: Option<&[u8]>, out: &mut Vec<u8>) -> Result<(), Error> {
+  validate_bounds(&Alphabet::BINARY, a, b)?;
+  write_key_between(&Alphabet::BINARY, a, b, out)
+}
+
+/// n_keys_between returns n keys between a and b that sorts bytewise.
+/// Either a or b can be empty. If a is empty it indicates smallest key,
+/// If b is empty it indicates largest key.
+/// b must be empty or > a.
+pub fn n_keys_between(a: &Option<Vec<u8>>, b: &Option<Vec<u8>>, n: usize) -> Result<Vec<Vec<u8>>, Error> {
+  let (a, b) = (a.as_deref(), b.as_deref());
+  validate_bounds(&Alphabet::BINARY, a, b)?;
+  n_keys_between_valid(&Alphabet::BINARY, a, b, n)
+}
+
+/// validate_key returns error if key is not a valid binary key.
+pub fn validate_key(key: &[u8]) -> Result<(), Error> {
+  validate_order_key(&Alphabet::BINARY, key)
+}
+
+/// from_key converts a base62 key to a binary key, digit by digit.
+/// Every valid base62 key converts, and order between keys is preserved.
+pub fn from_key(key: &str) -> Result<Vec<u8>, Error> {
+  validate_order_key(&Alphabet::BASE62, key.as_bytes())?;
+  Alphabet::BASE62.transcode(key.as_bytes(), &Alphabet::BINARY)
+}
+
+/// to_key converts a binary key back to a base62 key.
+/// Only keys whose integer part fits the base62 range and whose digits are
+/// all below 62 are representable, others return an error.
+/// `to_key(&from_key(k)?)` always gives `k` back.
+pub fn to_key(key: &[u8]) -> Result<String, Error> {
+  validate_key(key)?;
+  Alphabet::BINARY.transcode(key, &Alphabet::BASE62).map(into_string)
+}
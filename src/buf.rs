@@ -0,0 +1,202 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use crate::error::key_string;
+use crate::Error;
+
+/// KeyBuf is a buffer keys can be written into, see `key_between_into`.
+/// Keys are only ever appended to it, or cut back to an earlier length.
+pub trait KeyBuf {
+  /// current length in bytes
+  fn len(&self) -> usize;
+
+  fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// content written so far
+  fn as_bytes(&self) -> &[u8];
+
+  /// appends one byte, returns error if the buffer is full
+  fn push(&mut self, byte: u8) -> Result<(), Error>;
+
+  fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), Error> {
+    for byte in bytes {
+      self.push(*byte)?;
+    }
+    Ok(())
+  }
+
+  /// shortens the buffer to len bytes
+  fn truncate(&mut self, len: usize);
+}
+
+#[cfg(feature = "alloc")]
+impl KeyBuf for Vec<u8> {
+  fn len(&self) -> usize {
+    Vec::len(self)
+  }
+
+  fn as_bytes(&self) -> &[u8] {
+    self
+  }
+
+  fn push(&mut self, byte: u8) -> Result<(), Error> {
+    Vec::push(self, byte);
+    Ok(())
+  }
+
+  fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), Error> {
+    Vec::extend_from_slice(self, bytes);
+    Ok(())
+  }
+
+  fn truncate(&mut self, len: usize) {
+    Vec::truncate(self, len)
+  }
+}
+
+/// keys of string alphabets are ASCII, each byte is pushed as a char.
+#[cfg(feature = "alloc")]
+impl KeyBuf for String {
+  fn len(&self) -> usize {
+    String::len(self)
+  }
+
+  fn as_bytes(&self) -> &[u8] {
+    String::as_bytes(self)
+  }
+
+  fn push(&mut self, byte: u8) -> Result<(), Error> {
+    debug_assert!(byte.is_ascii());
+    String::push(self, byte as char);
+    Ok(())
+  }
+
+  fn truncate(&mut self, len: usize) {
+    String::truncate(self, len)
+  }
+}
+
+/// StackKey is a fixed-capacity key of up to N bytes, stored inline.
+/// Writing a longer key into it fails with `Error::BufferFull`.
+#[derive(Clone, Copy)]
+pub struct StackKey<const N: usize> {
+  bytes: [u8; N],
+  len: usize,
+}
+
+impl<const N: usize> StackKey<N> {
+  pub fn new() -> Self {
+    StackKey { bytes: [0; N], len: 0 }
+  }
+
+  pub fn capacity(&self) -> usize {
+    N
+  }
+
+  pub fn clear(&mut self) {
+    self.len = 0;
+  }
+
+  pub fn as_str(&self) -> &str {
+    core::str::from_utf8(KeyBuf::as_bytes(self)).expect("alphabet is ASCII")
+  }
+}
+
+impl<const N: usize> KeyBuf for StackKey<N> {
+  fn len(&self) -> usize {
+    self.len
+  }
+
+  fn as_bytes(&self) -> &[u8] {
+    &self.bytes[..self.len]
+  }
+
+  fn push(&mut self, byte: u8) -> Result<(), Error> {
+    debug_assert!(byte.is_ascii());
+    if self.len == N {
+      return Err(Error::BufferFull { capacity: N });
+    }
+    self.bytes[self.len] = byte;
+    self.len += 1;
+    Ok(())
+  }
+
+  fn truncate(&mut self, len: usize) {
+    self.len = self.len.min(len);
+  }
+}
+
+impl<const N: usize> Default for StackKey<N> {
+  fn default() -> Self {
+    StackKey::new()
+  }
+}
+
+/// copies a key, e.g. one read from storage, failing with `Error::BufferFull` if it
+/// is longer than N bytes. The key isn't validated, only checked to be ASCII.
+impl<const N: usize> TryFrom<&str> for StackKey<N> {
+  type Error = Error;
+
+  fn try_from(key: &str) -> Result<Self, Self::Error> {
+    if let Some(position) = key.bytes().position(|byte| !byte.is_ascii()) {
+      return Err(Error::InvalidDigit {
+        key: key_string(key.as_bytes()),
+        position,
+      });
+    }
+    let mut out = StackKey::new();
+    out.extend_from_slice(key.as_bytes())?;
+    Ok(out)
+  }
+}
+
+impl<const N: usize> AsRef<str> for StackKey<N> {
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
+
+impl<const N: usize> fmt::Display for StackKey<N> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl<const N: usize> fmt::Debug for StackKey<N> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(self.as_str(), f)
+  }
+}
+
+impl<const N: usize> PartialEq for StackKey<N> {
+  fn eq(&self, other: &Self) -> bool {
+    self.as_str() == other.as_str()
+  }
+}
+
+impl<const N: usize> Eq for StackKey<N> {}
+
+impl<const N: usize> PartialOrd for StackKey<N> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<const N: usize> Ord for StackKey<N> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.as_str().cmp(other.as_str())
+  }
+}
+
+impl<const N: usize> Hash for StackKey<N> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_str().hash(state)
+  }
+}
@@ -0,0 +1,65 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{into_string, Alphabet, Error};
+
+impl Alphabet {
+  /// collation_corpus returns keys of this alphabet, sorted, covering the known ways a
+  /// collation misorders keys: case folding, digits interleaved with letters and
+  /// shorter keys sorting after longer ones. It has an integer key per head and digit,
+  /// and the keys after zero with one or two fractional digits, so every pair of
+  /// adjacent characters is compared at the start, in the integer part and in the
+  /// fractional part. A collation that sorts the corpus right is only known to get
+  /// these cases right, it is no guarantee for every key.
+  ///
+  /// To check a database collation, insert the corpus and compare it with the
+  /// result of `ORDER BY`.
+  pub fn collation_corpus(&self) -> Vec<String> {
+    let mut keys = vec![];
+    for head in self.heads().bytes() {
+      let int_len = self.int_len(head).unwrap();
+      for digit in self.digits().bytes() {
+        let mut int = vec![head];
+        int.resize(int_len, digit);
+        if !self.is_smallest_int(&int) {
+          keys.push(int);
+        }
+      }
+    }
+    let zero = self.zero();
+    let fraction_digits = || self.digits().bytes().filter(|d| *d != self.zero_digit());
+    for d1 in self.digits().bytes() {
+      if d1 != self.zero_digit() {
+        keys.push([&zero[..], &[d1]].concat());
+      }
+      for d2 in fraction_digits() {
+        keys.push([&zero[..], &[d1, d2]].concat());
+      }
+    }
+    keys.sort();
+    keys.dedup();
+    keys.into_iter().map(into_string).collect()
+  }
+
+  /// verify_collation checks that compare orders the keys of `collation_corpus` the way
+  /// this crate does, as byte strings. It fails with `Error::CollationMismatch` on the first
+  /// pair of `collation_corpus` keys compare doesn't order, so compare is called
+  /// once per key of the corpus, and must be a total order.
+  ///
+  /// Base62 keys mix cases, they don't sort right under case-insensitive collations,
+  /// `Alphabet::BASE36` and `Alphabet::HEX` keys do.
+  pub fn verify_collation<F: FnMut(&str, &str) -> Ordering>(&self, mut compare: F) -> Result<(), Error> {
+    let keys = self.collation_corpus();
+    for pair in keys.windows(2) {
+      if compare(&pair[0], &pair[1]) != Ordering::Less {
+        return Err(Error::CollationMismatch {
+          a: pair[0].clone().into(),
+          b: pair[1].clone().into(),
+        });
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,277 @@
+//! Operation-based replicated sequence, positions being fractional keys.
+//!
+//! Every replica applies its own edits to a `Sequence` and broadcasts the returned
+//! `Op`s, which other replicas pass to `apply`. Ops commute and are idempotent, so
+//! replicas that applied the same set of ops, in any order and with duplicates,
+//! have the same content.
+//!
+//! - keys are generated with `key_between_with_replica`, so concurrent inserts at
+//!   the same place get distinct keys, ordered by replica id.
+//! - an element moves by getting a new key, the move with the greatest `OpId`
+//!   wins over concurrent ones.
+//! - deleted elements stay as tombstones, keeping their key so that later keys
+//!   never collide with it, until `purge_tombstones` drops them.
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+
+use crate::{Error, FractionalIndex};
+
+/// OpId identifies an op, and the element an insert op creates. Ops are ordered
+/// by their Lamport clock, then by replica, which orders concurrent ops the same
+/// way on every replica.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpId {
+  pub clock: u64,
+  pub replica: u64,
+}
+
+/// Op is an edit to broadcast to the other replicas.
+/// With the `serde` feature, keys of deserialized ops are validated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Op<T> {
+  /// creates element id at key
+  Insert { id: OpId, key: FractionalIndex, value: T },
+  /// deletes element, whether it was inserted already or not
+  Delete { id: OpId, element: OpId },
+  /// moves element to key
+  Move {
+    id: OpId,
+    element: OpId,
+    key: FractionalIndex,
+  },
+}
+
+impl<T> Op<T> {
+  pub fn id(&self) -> OpId {
+    match self {
+      Op::Insert { id, .. } | Op::Delete { id, .. } | Op::Move { id, .. } => *id,
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+struct Element<T> {
+  /// None until an op placed the element
+  key: Option<FractionalIndex>,
+  /// op that set key, the insert or the winning move
+  placed_by: OpId,
+  /// None until the insert op is applied
+  value: Option<T>,
+  deleted: bool,
+}
+
+impl<T> Element<T> {
+  fn visible(&self) -> bool {
+    self.value.is_some() && !self.deleted
+  }
+}
+
+/// Sequence is one replica of a replicated list of T.
+#[derive(Clone, Debug)]
+pub struct Sequence<T> {
+  replica: u64,
+  clock: u64,
+  elements: HashMap<OpId, Element<T>>,
+  /// every element, tombstones included, by key. Equal keys, possible while a move
+  /// is not applied everywhere, are ordered by element id.
+  order: BTreeSet<(FractionalIndex, OpId)>,
+}
+
+impl<T: Clone> Sequence<T> {
+  /// new returns an empty sequence for replica, which must be unique among replicas
+  /// and fit the base62 replica ids of `key_between_with_replica`.
+  pub fn new(replica: u64) -> Self {
+    Sequence {
+      replica,
+      clock: 0,
+      elements: HashMap::new(),
+      order: BTreeSet::new(),
+    }
+  }
+
+  pub fn replica(&self) -> u64 {
+    self.replica
+  }
+
+  /// number of elements, tombstones excluded
+  pub fn len(&self) -> usize {
+    self.elements.values().filter(|element| element.visible()).count()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// number of deleted elements still kept
+  pub fn tombstones(&self) -> usize {
+    self.elements.values().filter(|element| element.deleted).count()
+  }
+
+  pub fn get(&self, index: usize) -> Option<&T> {
+    self.iter().nth(index)
+  }
+
+  /// id of the element at index, stable across moves and replicas
+  pub fn id_at(&self, index: usize) -> Option<OpId> {
+    self.visible().nth(index)
+  }
+
+  /// iter visits the elements in order, tombstones excluded.
+  pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+    self.visible().map(move |id| self.elements[&id].value.as_ref().unwrap())
+  }
+
+  /// insert inserts value at index, and returns the op to broadcast.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index > len`.
+  pub fn insert(&mut self, index: usize, value: T) -> Result<Op<T>, Error> {
+    assert!(index <= self.len(), "insertion index {} out of bounds", index);
+    let key = self.key_at(index, None)?;
+    let op = Op::Insert {
+      id: self.tick(),
+      key,
+      value,
+    };
+    self.apply(op.clone());
+    Ok(op)
+  }
+
+  /// delete deletes the element at index, and returns the op to broadcast.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index >= len`.
+  pub fn delete(&mut self, index: usize) -> Op<T> {
+    let element = self.id_at(index).expect("deletion index out of bounds");
+    let op = Op::Delete {
+      id: self.tick(),
+      element,
+    };
+    self.apply(op.clone());
+    op
+  }
+
+  /// move_to moves the element at from so that it is at index to,
+  /// and returns the op to broadcast.
+  ///
+  /// # Panics
+  ///
+  /// Panics if from or to is out of bounds.
+  pub fn move_to(&mut self, from: usize, to: usize) -> Result<Op<T>, Error> {
+    let element = self.id_at(from).expect("move index out of bounds");
+    assert!(to < self.len(), "move index {} out of bounds", to);
+    let key = self.key_at(to, Some(element))?;
+    let op = Op::Move {
+      id: self.tick(),
+      element,
+      key,
+    };
+    self.apply(op.clone());
+    Ok(op)
+  }
+
+  /// apply applies an op of any replica. Applying an op again does nothing.
+  pub fn apply(&mut self, op: Op<T>) {
+    self.clock = self.clock.max(op.id().clock);
+    match op {
+      Op::Insert { id, key, value } => {
+        let element = self.element(id);
+        if element.value.is_none() {
+          element.value = Some(value);
+        }
+        self.place(id, key, id);
+      }
+      Op::Delete { element, .. } => {
+        // a delete can arrive before the insert, the element waits for it as a tombstone
+        self.element(element).deleted = true;
+      }
+      Op::Move { id, element, key } => {
+        self.element(element);
+        self.place(element, key, id);
+      }
+    }
+  }
+
+  /// purge_tombstones drops deleted elements. Only safe once every replica applied
+  /// every op about them, otherwise a late insert would bring them back.
+  pub fn purge_tombstones(&mut self) {
+    let order = &mut self.order;
+    self.elements.retain(|id, element| {
+      if let (true, Some(key)) = (element.deleted, element.key.take()) {
+        order.remove(&(key, *id));
+      }
+      !element.deleted
+    });
+  }
+
+  fn tick(&mut self) -> OpId {
+    self.clock += 1;
+    OpId {
+      clock: self.clock,
+      replica: self.replica,
+    }
+  }
+
+  fn visible(&self) -> impl Iterator<Item = OpId> + '_ {
+    self
+      .order
+      .iter()
+      .map(|(_, id)| *id)
+      .filter(move |id| self.elements[id].visible())
+  }
+
+  /// the element id, created unplaced if unknown yet
+  fn element(&mut self, id: OpId) -> &mut Element<T> {
+    self.elements.entry(id).or_insert(Element {
+      key: None,
+      placed_by: OpId { clock: 0, replica: 0 },
+      value: None,
+      deleted: false,
+    })
+  }
+
+  /// moves element id to key, unless a later op placed it already
+  fn place(&mut self, id: OpId, key: FractionalIndex, op: OpId) {
+    let element = self.elements.get_mut(&id).unwrap();
+    if op <= element.placed_by {
+      return;
+    }
+    if let Some(old) = element.key.replace(key.to_owned()) {
+      self.order.remove(&(old, id));
+    }
+    self.order.insert((key, id));
+    element.placed_by = op;
+  }
+
+  /// a new key right after the visible element before index, skipping element skip.
+  /// The key goes before the tombstones following that element, so it never equals theirs.
+  fn key_at(&self, index: usize, skip: Option<OpId>) -> Result<FractionalIndex, Error> {
+    let entries: Vec<&(FractionalIndex, OpId)> = self.order.iter().filter(|(_, id)| Some(*id) != skip).collect();
+    let visible = |(_, id): &&(FractionalIndex, OpId)| self.elements[id].visible();
+    // position in entries after which the key goes
+    let after = match index.checked_sub(1) {
+      Some(i) => entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| visible(entry))
+        .nth(i)
+        .map(|(p, _)| p),
+      None => None,
+    };
+    let a = after.map(|p| &entries[p].0);
+    let start = after.map_or(0, |p| p + 1);
+    // equal keys can't bound a key, the key goes after all of them
+    let b = entries[start..]
+      .iter()
+      .map(|(key, _)| key)
+      .find(|key| a.is_none_or(|a| a < *key));
+    FractionalIndex::with_replica(a, b, self.replica)
+  }
+}
@@ -0,0 +1,29 @@
+use alloc::string::String;
+use core::convert::TryFrom;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+
+use crate::FractionalIndex;
+
+impl<DB: Backend> ToSql<Text, DB> for FractionalIndex
+where
+  str: ToSql<Text, DB>,
+{
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    self.as_str().to_sql(out)
+  }
+}
+
+/// reads Text, rejecting invalid keys.
+impl<DB: Backend> FromSql<Text, DB> for FractionalIndex
+where
+  String: FromSql<Text, DB>,
+{
+  fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+    let key = String::from_sql(bytes)?;
+    Ok(FractionalIndex::try_from(key)?)
+  }
+}
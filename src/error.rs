@@ -0,0 +1,157 @@
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+use core::fmt;
+
+#[cfg(not(feature = "alloc"))]
+use crate::buf::StackKey;
+#[cfg(not(feature = "alloc"))]
+use crate::KeyBuf;
+
+/// ErrorKey is a key, or alphabet, carried by an error. It is the same type with and
+/// without the `alloc` feature, but without it only the first 32 bytes are kept.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ErrorKey(Repr);
+
+#[cfg(feature = "alloc")]
+type Repr = String;
+#[cfg(not(feature = "alloc"))]
+type Repr = StackKey<ERROR_KEY_CAPACITY>;
+
+#[cfg(not(feature = "alloc"))]
+const ERROR_KEY_CAPACITY: usize = 32;
+
+impl ErrorKey {
+  pub fn as_str(&self) -> &str {
+    self.0.as_str()
+  }
+}
+
+impl fmt::Display for ErrorKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl fmt::Debug for ErrorKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(self.as_str(), f)
+  }
+}
+
+impl From<&str> for ErrorKey {
+  fn from(key: &str) -> Self {
+    key_string(key.as_bytes())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl From<String> for ErrorKey {
+  fn from(key: String) -> Self {
+    ErrorKey(key)
+  }
+}
+
+impl PartialEq<str> for ErrorKey {
+  fn eq(&self, other: &str) -> bool {
+    self.as_str() == other
+  }
+}
+
+impl PartialEq<&str> for ErrorKey {
+  fn eq(&self, other: &&str) -> bool {
+    self.as_str() == *other
+  }
+}
+
+/// Error is returned by every fallible function of this crate.
+/// Display keeps the messages of the former `String` errors. New variants may be
+/// added, so matches need a wildcard arm.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+  /// key is an empty string
+  EmptyKey,
+  /// first character of key is not a head of the alphabet (`A-Z`, `a-z` for base62)
+  InvalidHead { key: ErrorKey },
+  /// key is shorter than the integer part its head requires
+  TruncatedInteger { key: ErrorKey, expected_len: usize },
+  /// fractional part of key ends with the zero digit (`0` for base62)
+  TrailingZero { key: ErrorKey },
+  /// key is the smallest integer, which is reserved so that a key before it always exists
+  SmallestKey { key: ErrorKey },
+  /// character at `position` of key is not a digit
+  InvalidDigit { key: ErrorKey, position: usize },
+  /// alphabet characters are not ASCII and strictly ascending from `position`,
+  /// or there are too few of them
+  InvalidAlphabet { chars: ErrorKey, position: usize },
+  /// replica id has more digits than the alphabet can count in one digit
+  ReplicaTooLarge { replica: u64 },
+  /// key doesn't end with a replica id
+  MissingReplica { key: ErrorKey },
+  /// key doesn't fit in a fixed-capacity buffer
+  BufferFull { capacity: usize },
+  /// lower bound is not less than upper bound
+  InvalidOrder { a: ErrorKey, b: ErrorKey },
+  /// no key exists before the lower end of the integer range
+  RangeUnderflow,
+  /// no key exists between a key at the upper end of the integer range and its upper bound
+  RangeOverflow,
+  /// element id is not in the list
+  UnknownId { id: u64 },
+  /// a collation doesn't order a before b, see `Alphabet::verify_collation`
+  CollationMismatch { a: ErrorKey, b: ErrorKey },
+  /// number is not finite, or has no finite expansion in the radix of the alphabet
+  Unrepresentable,
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::EmptyKey => write!(f, "invalid order key"),
+      Error::InvalidHead { key } => match key.as_str().chars().next() {
+        Some(head) => write!(f, "invalid order key head: {}", head),
+        None => write!(f, "invalid order key"),
+      },
+      Error::TruncatedInteger { key, .. }
+      | Error::TrailingZero { key }
+      | Error::SmallestKey { key }
+      | Error::InvalidDigit { key, .. } => write!(f, "invalid order key: {}", key),
+      Error::InvalidAlphabet { chars, .. } => write!(f, "invalid alphabet: {}", chars),
+      Error::ReplicaTooLarge { replica } => write!(f, "replica id too large: {}", replica),
+      Error::MissingReplica { key } => write!(f, "no replica id in order key: {}", key),
+      Error::BufferFull { capacity } => write!(f, "key exceeds buffer capacity: {}", capacity),
+      Error::InvalidOrder { a, b } => write!(f, "invalid order: {} >= {}", a, b),
+      Error::RangeUnderflow => write!(f, "range underflow"),
+      Error::RangeOverflow => write!(f, "range overflow"),
+      Error::UnknownId { id } => write!(f, "unknown element id: {}", id),
+      Error::CollationMismatch { a, b } => write!(f, "collation doesn't order {} before {}", a, b),
+      Error::Unrepresentable => write!(f, "number not representable as order key"),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// key_string renders a key for an error, escaping it when it isn't UTF-8
+#[cfg(feature = "alloc")]
+pub(crate) fn key_string(key: &[u8]) -> ErrorKey {
+  match core::str::from_utf8(key) {
+    Ok(key) => ErrorKey(key.to_string()),
+    Err(_) => ErrorKey(key.escape_ascii().to_string()),
+  }
+}
+
+/// key_string renders a key for an error, escaped when it isn't ASCII,
+/// and cut at `ERROR_KEY_CAPACITY` bytes
+#[cfg(not(feature = "alloc"))]
+pub(crate) fn key_string(key: &[u8]) -> ErrorKey {
+  let mut out = StackKey::new();
+  if key.is_ascii() {
+    let _ = out.extend_from_slice(&key[..key.len().min(ERROR_KEY_CAPACITY)]);
+  } else {
+    // stops at the first byte that doesn't fit
+    let _ = key.escape_ascii().try_for_each(|byte| out.push(byte));
+  }
+  ErrorKey(out)
+}
@@ -0,0 +1,173 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::replica::replica_key_valid;
+use crate::{into_string, key_between_valid, validate_order_key, Alphabet, Error};
+
+/// FractionalIndex is an order key that has already been validated.
+/// It can only be built through `parse` (or `TryFrom`/`FromStr`), so
+/// generating new keys from it skips the validation `key_between` does.
+/// Ordering is the lexicographic order of the underlying string.
+///
+/// # Databases
+///
+/// The `rusqlite`, `sqlx` and `diesel` features store it as TEXT, and validate the
+/// keys read back. `ORDER BY` agrees with this ordering as long as the column compares
+/// bytes: SQLite's default BINARY collation, `COLLATE "C"` in Postgres, a `_bin`
+/// collation in MySQL. Locale or case-insensitive collations don't, `a0` and `A0`
+/// being different keys.
+///
+/// ```
+/// use lexicon_fractional_index::{key_between, n_keys_between};
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let conn = rusqlite::Connection::open_in_memory()?;
+/// conn.execute("CREATE TABLE items (key TEXT NOT NULL COLLATE BINARY)", ())?;
+/// let mut keys = n_keys_between(&Some("Zz".to_owned()), &Some("a1".to_owned()), 100)?;
+/// keys.push(key_between(&None, &Some("A00000000000000000000000001".to_owned()))?);
+/// keys.push(key_between(&Some("zzzzzzzzzzzzzzzzzzzzzzzzzzz".to_owned()), &None)?);
+/// for key in keys.iter().rev() {
+///   conn.execute("INSERT INTO items (key) VALUES (?1)", [key])?;
+/// }
+///
+/// let mut select = conn.prepare("SELECT key FROM items ORDER BY key")?;
+/// let sorted = select.query_map((), |row| row.get(0))?.collect::<Result<Vec<String>, _>>()?;
+/// keys.sort();
+/// assert_eq!(sorted, keys);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+  feature = "diesel",
+  derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+  diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct FractionalIndex(pub(crate) String);
+
+impl FractionalIndex {
+  /// parse validates key and wraps it.
+  pub fn parse(key: &str) -> Result<Self, Error> {
+    validate_order_key(&Alphabet::BASE62, key.as_bytes())?;
+    Ok(FractionalIndex(key.to_owned()))
+  }
+
+  /// new_unchecked wraps key without validating it, for keys that were validated before,
+  /// e.g. when they were stored. key MUST be valid, otherwise the keys generated from it
+  /// are unspecified, see `Alphabet::key_between_unchecked`. Debug builds assert it.
+  pub fn new_unchecked(key: String) -> Self {
+    debug_assert_eq!(
+      validate_order_key(&Alphabet::BASE62, key.as_bytes()),
+      Ok(()),
+      "unchecked key"
+    );
+    FractionalIndex(key)
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+
+  pub fn into_string(self) -> String {
+    self.0
+  }
+
+  /// between returns a key that sorts between self and other.
+  /// other must be > self.
+  pub fn between(&self, other: &FractionalIndex) -> Result<FractionalIndex, Error> {
+    if self >= other {
+      return Err(Error::InvalidOrder {
+        a: self.0.clone().into(),
+        b: other.0.clone().into(),
+      });
+    }
+    key_between_valid(&Alphabet::BASE62, Some(self.0.as_bytes()), Some(other.0.as_bytes()))
+      .map(|key| FractionalIndex(into_string(key)))
+  }
+
+  /// before returns a key that sorts before self.
+  /// Before `A00000000000000000000000001`, `key_between` returns the reserved smallest
+  /// integer as the JS and Go implementations do, before returns `A00000000000000000000000000V`.
+  pub fn before(&self) -> Result<FractionalIndex, Error> {
+    let alphabet = &Alphabet::BASE62;
+    let mut key = key_between_valid(alphabet, None, Some(self.0.as_bytes()))?;
+    if alphabet.is_smallest_int(&key) {
+      key = key_between_valid(alphabet, Some(&key), Some(self.0.as_bytes()))?;
+    }
+    Ok(FractionalIndex(into_string(key)))
+  }
+
+  /// after returns a key that sorts after self.
+  pub fn after(&self) -> Result<FractionalIndex, Error> {
+    key_between_valid(&Alphabet::BASE62, Some(self.0.as_bytes()), None).map(|key| FractionalIndex(into_string(key)))
+  }
+}
+
+impl FractionalIndex {
+  /// same as `key_between_with_replica`, but a MUST be < b.
+  #[cfg_attr(not(feature = "crdt"), allow(dead_code))]
+  pub(crate) fn with_replica(
+    a: Option<&FractionalIndex>,
+    b: Option<&FractionalIndex>,
+    replica: u64,
+  ) -> Result<FractionalIndex, Error> {
+    let (a, b) = (a.map(|a| a.0.as_bytes()), b.map(|b| b.0.as_bytes()));
+    replica_key_valid(&Alphabet::BASE62, a, b, replica).map(|key| FractionalIndex(into_string(key)))
+  }
+}
+
+/// the key `key_between` returns when both bounds are empty.
+impl Default for FractionalIndex {
+  fn default() -> Self {
+    FractionalIndex(into_string(Alphabet::BASE62.zero()))
+  }
+}
+
+impl fmt::Display for FractionalIndex {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl AsRef<str> for FractionalIndex {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl FromStr for FractionalIndex {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    FractionalIndex::parse(s)
+  }
+}
+
+impl TryFrom<&str> for FractionalIndex {
+  type Error = Error;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    FractionalIndex::parse(value)
+  }
+}
+
+impl TryFrom<String> for FractionalIndex {
+  type Error = Error;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    validate_order_key(&Alphabet::BASE62, value.as_bytes())?;
+    Ok(FractionalIndex(value))
+  }
+}
+
+impl From<FractionalIndex> for String {
+  fn from(index: FractionalIndex) -> Self {
+    index.0
+  }
+}
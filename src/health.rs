@@ -0,0 +1,116 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::rebalance::{grow_window, validate_sorted};
+use crate::{get_int_part, Alphabet, Error};
+
+/// number of keys reported in `KeyStats::longest`
+const LONGEST_COUNT: usize = 10;
+
+/// KeyStats describes the keys of a sorted list, see `Alphabet::analyze`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyStats {
+  /// number of keys
+  pub count: usize,
+  /// `histogram[len]` is the number of keys len bytes long
+  pub histogram: Vec<usize>,
+  pub mean_len: f64,
+  pub max_len: usize,
+  /// longest fractional part, it grows with insertions between close keys
+  pub max_fraction_len: usize,
+  /// indexes of the (at most 10) longest keys, longest first
+  pub longest: Vec<usize>,
+  /// heads left below the integer part of the first key, before the smallest integer
+  /// (`A00000000000000000000000000` for base62). Each one is a longer integer part.
+  pub heads_below: usize,
+  /// heads left above the integer part of the last key, before the largest integer
+  /// (`zzzzzzzzzzzzzzzzzzzzzzzzzzz` for base62). Each one is a longer integer part.
+  pub heads_above: usize,
+  /// disjoint windows to rewrite with `rebalance_window` so that no key is longer
+  /// than the limit given to `analyze`, in ascending order
+  pub windows: Vec<Range<usize>>,
+}
+
+impl KeyStats {
+  /// whether some keys are too long, or the integer parts reached an end of their range
+  pub fn needs_rebalance(&self) -> bool {
+    !self.windows.is_empty() || self.heads_below == 0 || self.heads_above == 0
+  }
+}
+
+impl Alphabet {
+  /// analyze reports the length distribution of sorted keys, their longest keys,
+  /// how many integer lengths are left at both ends, and the windows to rebalance
+  /// so that no key is longer than max_len.
+  /// keys must be valid and strictly ascending.
+  pub fn analyze<K: AsRef<str>>(&self, keys: &[K], max_len: usize) -> Result<KeyStats, Error> {
+    validate_sorted(self, keys)?;
+
+    let mut histogram = vec![];
+    let mut max_fraction_len = 0;
+    for key in keys {
+      let key = key.as_ref().as_bytes();
+      if histogram.len() <= key.len() {
+        histogram.resize(key.len() + 1, 0);
+      }
+      histogram[key.len()] += 1;
+      max_fraction_len = max_fraction_len.max(key.len() - get_int_part(self, key)?.len());
+    }
+    let total_len: usize = keys.iter().map(|key| key.as_ref().len()).sum();
+
+    let mut longest: Vec<usize> = (0..keys.len()).collect();
+    longest.sort_by_key(|i| core::cmp::Reverse(keys[*i].as_ref().len()));
+    longest.truncate(LONGEST_COUNT);
+
+    let head_of = |key: &K| self.head_index(key.as_ref().as_bytes()[0]).unwrap();
+    let heads_below = keys.first().map_or(self.half(), head_of);
+    let heads_above = self.half() * 2 - 1 - keys.last().map_or(self.half(), head_of);
+
+    Ok(KeyStats {
+      count: keys.len(),
+      max_len: histogram.len().saturating_sub(1),
+      histogram,
+      mean_len: if keys.is_empty() {
+        0.0
+      } else {
+        total_len as f64 / keys.len() as f64
+      },
+      max_fraction_len,
+      longest,
+      heads_below,
+      heads_above,
+      windows: windows(self, keys, max_len)?,
+    })
+  }
+}
+
+/// one window around every run of keys longer than max_len,
+/// runs whose windows overlap or touch share one.
+fn windows<K: AsRef<str>>(alphabet: &Alphabet, keys: &[K], max_len: usize) -> Result<Vec<Range<usize>>, Error> {
+  let mut windows: Vec<Range<usize>> = vec![];
+  let mut i = 0;
+  while i < keys.len() {
+    if keys[i].as_ref().len() <= max_len {
+      i += 1;
+      continue;
+    }
+    let start = i;
+    while i < keys.len() && keys[i].as_ref().len() > max_len {
+      i += 1;
+    }
+    let (mut window, _) = grow_window(alphabet, keys, start..i, max_len)?;
+    // the keys around a window bound its new keys, they can't be rewritten themselves
+    while let Some(last) = windows.last() {
+      if window.start > last.end {
+        break;
+      }
+      let merged = last.start..window.end;
+      windows.pop();
+      window = grow_window(alphabet, keys, merged, max_len)?.0;
+    }
+    i = i.max(window.end);
+    windows.push(window);
+  }
+  Ok(windows)
+}
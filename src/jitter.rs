@@ -0,0 +1,102 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{into_string, key_between_valid, n_keys_between_valid, validate_bounds, Alphabet, Error};
+
+/// RandomSource is the randomness jittered keys are drawn from.
+/// Closures returning `u64` implement it, e.g. `|| rng.gen::<u64>()` with `rand`.
+pub trait RandomSource {
+  fn next_u64(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> RandomSource for F {
+  fn next_u64(&mut self) -> u64 {
+    self()
+  }
+}
+
+impl Alphabet {
+  /// key_between_jittered returns a random key that sorts lexicographically between a and b,
+  /// so that concurrent calls with the same bounds almost never return the same key.
+  /// The interval is bisected `bits` times, following one random bit each time, which
+  /// picks one of `2^bits` disjoint sub-intervals and makes the key about
+  /// `bits / log2(radix)` digits longer than `key_between` would.
+  /// Either a or b can be empty strings. If a is empty it indicates smallest key,
+  /// If b is empty it indicates largest key.
+  /// b must be empty string or > a.
+  pub fn key_between_jittered<R: RandomSource + ?Sized>(
+    &self,
+    a: &Option<String>,
+    b: &Option<String>,
+    rng: &mut R,
+    bits: u32,
+  ) -> Result<String, Error> {
+    let (a, b) = (a.as_deref().map(str::as_bytes), b.as_deref().map(str::as_bytes));
+    validate_bounds(self, a, b)?;
+    jitter_valid(self, a, b, rng, bits).map(into_string)
+  }
+
+  /// n_keys_between_jittered returns n random keys between a and b that sorts lexicographically.
+  /// The keys are spread like `n_keys_between`, each one jittered with `bits` random bits
+  /// as in `key_between_jittered`.
+  pub fn n_keys_between_jittered<R: RandomSource + ?Sized>(
+    &self,
+    a: &Option<String>,
+    b: &Option<String>,
+    n: usize,
+    rng: &mut R,
+    bits: u32,
+  ) -> Result<Vec<String>, Error> {
+    let (a, b) = (a.as_deref().map(str::as_bytes), b.as_deref().map(str::as_bytes));
+    validate_bounds(self, a, b)?;
+    let keys = n_keys_jitter_valid(self, a, b, n, rng, bits)?;
+    Ok(keys.into_iter().map(into_string).collect())
+  }
+}
+
+/// same as key_between_jittered, but a and b MUST already be validated and ordered.
+pub(crate) fn jitter_valid<R: RandomSource + ?Sized>(
+  alphabet: &Alphabet,
+  a: Option<&[u8]>,
+  b: Option<&[u8]>,
+  rng: &mut R,
+  bits: u32,
+) -> Result<Vec<u8>, Error> {
+  let mut lo = a.map(<[u8]>::to_vec);
+  let mut hi = b.map(<[u8]>::to_vec);
+  let mut random = 0;
+  for i in 0..bits {
+    if i % 64 == 0 {
+      random = rng.next_u64();
+    }
+    let mid = key_between_valid(alphabet, lo.as_deref(), hi.as_deref())?;
+    if random & 1 == 0 {
+      hi = Some(mid);
+    } else {
+      lo = Some(mid);
+    }
+    random >>= 1;
+  }
+  key_between_valid(alphabet, lo.as_deref(), hi.as_deref())
+}
+
+/// same as n_keys_between_jittered, but a and b MUST already be validated and ordered.
+/// Key i is jittered between key i-1 and the unjittered key i+1.
+pub(crate) fn n_keys_jitter_valid<R: RandomSource + ?Sized>(
+  alphabet: &Alphabet,
+  a: Option<&[u8]>,
+  b: Option<&[u8]>,
+  n: usize,
+  rng: &mut R,
+  bits: u32,
+) -> Result<Vec<Vec<u8>>, Error> {
+  let spread = n_keys_between_valid(alphabet, a, b, n)?;
+  let mut result: Vec<Vec<u8>> = Vec::with_capacity(n);
+  for i in 0..n {
+    let lo = result.last().map(Vec::as_slice).or(a);
+    let hi = spread.get(i + 1).map(Vec::as_slice).or(b);
+    let key = jitter_valid(alphabet, lo, hi, rng, bits)?;
+    result.push(key);
+  }
+  Ok(result)
+}
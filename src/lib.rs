@@ -1,379 +1,641 @@
-const BASE62_DIGITS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
-const SMALLEST_INT: &str = "A00000000000000000000000000";
-const ZERO: &str = "a0";
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod alphabet;
+#[cfg(feature = "alloc")]
+mod balanced;
+#[cfg(feature = "alloc")]
+pub mod binary;
+mod buf;
+#[cfg(feature = "alloc")]
+mod collation;
+#[cfg(feature = "crdt")]
+pub mod crdt;
+#[cfg(feature = "diesel")]
+mod diesel_impl;
+mod error;
+#[cfg(feature = "alloc")]
+mod fractional_index;
+#[cfg(feature = "alloc")]
+mod health;
+#[cfg(feature = "alloc")]
+mod jitter;
+#[cfg(feature = "std")]
+mod list;
+#[cfg(feature = "alloc")]
+mod migrate;
+#[cfg(feature = "rational")]
+mod rational;
+#[cfg(feature = "alloc")]
+mod rebalance;
+#[cfg(feature = "alloc")]
+mod reorder;
+#[cfg(feature = "alloc")]
+mod replica;
+#[cfg(feature = "rusqlite")]
+mod rusqlite_impl;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "sqlx")]
+mod sqlx_impl;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+
+pub use alphabet::Alphabet;
+pub use buf::{KeyBuf, StackKey};
+pub use error::{Error, ErrorKey};
+#[cfg(feature = "alloc")]
+pub use fractional_index::FractionalIndex;
+#[cfg(feature = "alloc")]
+pub use health::KeyStats;
+#[cfg(feature = "alloc")]
+pub use jitter::RandomSource;
+#[cfg(feature = "std")]
+pub use list::{ElementId, FractionalList};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
+use error::key_string;
 
 /// key_between returns a key that sorts lexicographically between a and b.
 /// Either a or b can be empty strings. If a is empty it indicates smallest key,
 /// If b is empty it indicates largest key.
 /// b must be empty string or > a.
-pub fn key_between(a: &Option<String>, b: &Option<String>) -> Result<String, String> {
-  // println!("between: {} {}", a, b);
-  if !a.is_none() {
-    validate_order_key(a.as_ref().unwrap())?;
-  }
-  if !b.is_none() {
-    validate_order_key(b.as_ref().unwrap())?;
+#[cfg(feature = "alloc")]
+pub fn key_between(a: &Option<String>, b: &Option<String>) -> Result<String, Error> {
+  Alphabet::BASE62.key_between(a, b)
+}
+
+/// key_between_into appends a key that sorts lexicographically between a and b to out,
+/// without allocating when out has room for it. See `Alphabet::key_between_into`.
+pub fn key_between_into<B: KeyBuf + ?Sized>(a: Option<&str>, b: Option<&str>, out: &mut B) -> Result<(), Error> {
+  Alphabet::BASE62.key_between_into(a, b, out)
+}
+
+/// key_between_stack returns a key that sorts lexicographically between a and b
+/// as a `StackKey` of up to N bytes. See `Alphabet::key_between_stack`.
+pub fn key_between_stack<const N: usize>(a: Option<&str>, b: Option<&str>) -> Result<StackKey<N>, Error> {
+  Alphabet::BASE62.key_between_stack(a, b)
+}
+
+/// key_between_jittered returns a random key that sorts lexicographically between a and b,
+/// drawn with `bits` random bits from rng. See `Alphabet::key_between_jittered`.
+#[cfg(feature = "alloc")]
+pub fn key_between_jittered<R: RandomSource + ?Sized>(
+  a: &Option<String>,
+  b: &Option<String>,
+  rng: &mut R,
+  bits: u32,
+) -> Result<String, Error> {
+  Alphabet::BASE62.key_between_jittered(a, b, rng, bits)
+}
+
+/// key_between_with_replica returns a key that sorts lexicographically between a and b
+/// and carries the replica id. See `Alphabet::key_between_with_replica`.
+#[cfg(feature = "alloc")]
+pub fn key_between_with_replica(a: &Option<String>, b: &Option<String>, replica: u64) -> Result<String, Error> {
+  Alphabet::BASE62.key_between_with_replica(a, b, replica)
+}
+
+/// replica_id returns the replica id of a key generated by key_between_with_replica.
+#[cfg(feature = "alloc")]
+pub fn replica_id(key: &str) -> Result<u64, Error> {
+  Alphabet::BASE62.replica_id(key)
+}
+
+/// validates both bounds and their order
+fn validate_bounds(alphabet: &Alphabet, a: Option<&[u8]>, b: Option<&[u8]>) -> Result<(), Error> {
+  if let Some(a) = a {
+    validate_order_key(alphabet, a)?;
   }
-  if !a.is_none() && !b.is_none() && a >= b {
-    return Err(format!(
-      "invalid order: {} >= {}",
-      a.as_deref().unwrap(),
-      b.as_deref().unwrap()
-    ));
+  if let Some(b) = b {
+    validate_order_key(alphabet, b)?;
   }
-  if a.is_none() {
-    if b.is_none() {
-      return Ok(ZERO.to_owned());
+  if let (Some(a), Some(b)) = (a, b) {
+    if a >= b {
+      return Err(Error::InvalidOrder {
+        a: key_string(a),
+        b: key_string(b),
+      });
     }
+  }
+  Ok(())
+}
 
-    let int_b = get_int_part(b.as_deref().unwrap())?;
+/// same as key_between, but a and b MUST already be validated and ordered.
+#[cfg(feature = "alloc")]
+fn key_between_valid(alphabet: &Alphabet, a: Option<&[u8]>, b: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+  let mut out = vec![];
+  write_key_between(alphabet, a, b, &mut out)?;
+  Ok(out)
+}
+
+/// same as key_between_valid, but the key is appended to out.
+/// On error out is left as it was.
+fn write_key_between<B: KeyBuf + ?Sized>(
+  alphabet: &Alphabet,
+  a: Option<&[u8]>,
+  b: Option<&[u8]>,
+  out: &mut B,
+) -> Result<(), Error> {
+  let start = out.len();
+  let res = write_key_between_unwound(alphabet, a, b, out);
+  if res.is_err() {
+    out.truncate(start);
+  }
+  res
+}
 
-    let float_part_b = &b.as_deref().unwrap()[int_b.len()..];
-    if int_b == SMALLEST_INT {
-      return Ok((int_b as String) + &midpoint("", float_part_b));
+fn write_key_between_unwound<B: KeyBuf + ?Sized>(
+  alphabet: &Alphabet,
+  a: Option<&[u8]>,
+  b: Option<&[u8]>,
+  out: &mut B,
+) -> Result<(), Error> {
+  let start = out.len();
+  match (a, b) {
+    (None, None) => {
+      out.push(alphabet.head(alphabet.half()))?;
+      out.push(alphabet.zero_digit())
     }
-    if int_b.as_str() < b.as_deref().unwrap() {
-      return Ok(int_b);
+    (None, Some(b)) => {
+      let int_b = get_int_part(alphabet, b)?;
+
+      let float_part_b = &b[int_b.len()..];
+      if alphabet.is_smallest_int(int_b) {
+        out.extend_from_slice(int_b)?;
+        return midpoint(alphabet, &[], float_part_b, out);
+      }
+      if int_b < b {
+        return out.extend_from_slice(int_b);
+      }
+      if !decrement_int(alphabet, int_b, out)? {
+        return Err(Error::RangeUnderflow);
+      }
+      Ok(())
     }
-    let res = decrement_int(&int_b)?;
+    (Some(a), None) => {
+      let int_a = get_int_part(alphabet, a)?;
 
-    if res.is_empty() {
-      return Err("range underflow".to_owned());
+      let float_part_a = &a[int_a.len()..];
+      if !increment_int(alphabet, int_a, out)? {
+        out.extend_from_slice(int_a)?;
+        return midpoint(alphabet, float_part_a, &[], out);
+      }
+      Ok(())
     }
-    return Ok(res);
-  }
+    (Some(a), Some(b)) => {
+      let int_a = get_int_part(alphabet, a)?;
 
-  if b.is_none() {
-    let int_a = get_int_part(a.as_deref().unwrap())?;
+      let float_part_a = &a[int_a.len()..];
+      let int_b = get_int_part(alphabet, b)?;
 
-    let float_part_a = &a.as_deref().unwrap()[int_a.len()..];
-    let i = increment_int(&int_a)?;
-    if i.is_empty() {
-      return Ok(int_a + &midpoint(float_part_a, ""));
+      let float_part_b = &b[int_b.len()..];
+      if int_a == int_b {
+        out.extend_from_slice(int_a)?;
+        return midpoint(alphabet, float_part_a, float_part_b, out);
+      }
+      if !increment_int(alphabet, int_a, out)? {
+        return Err(Error::RangeOverflow);
+      }
+      if &out.as_bytes()[start..] < b {
+        return Ok(());
+      }
+      out.truncate(start);
+      out.extend_from_slice(int_a)?;
+      midpoint(alphabet, float_part_a, &[], out)
     }
-    return Ok(i);
   }
+}
 
-  let int_a = get_int_part(a.as_deref().unwrap())?;
-
-  let float_part_a = &a.as_deref().unwrap()[int_a.len()..];
-  let int_b = get_int_part(b.as_ref().unwrap())?;
+/// same as n_keys_between, but a and b MUST already be validated and ordered.
+#[cfg(feature = "alloc")]
+fn n_keys_between_valid(
+  alphabet: &Alphabet,
+  a: Option<&[u8]>,
+  b: Option<&[u8]>,
+  n: usize,
+) -> Result<Vec<Vec<u8>>, Error> {
+  if n == 0 {
+    return Ok(vec![]);
+  }
+  if n == 1 {
+    let c = key_between_valid(alphabet, a, b)?;
 
-  let float_part_b = &b.as_ref().unwrap()[int_b.len()..];
-  if int_a == int_b {
-    return Ok(int_a + &midpoint(float_part_a, float_part_b));
+    return Ok(vec![c]);
   }
-  let i = increment_int(&int_a)?;
+  if b.is_none() {
+    let mut c = key_between_valid(alphabet, a, b)?;
+    let mut result: Vec<Vec<u8>> = Vec::with_capacity(n);
+    result.push(c.to_owned());
 
-  if i.is_empty() {
-    return Err("range overflow".to_owned());
+    for _i in 0..(n - 1) {
+      c = key_between_valid(alphabet, Some(&c), b)?;
+      result.push(c.to_owned());
+    }
+
+    return Ok(result);
   }
-  if i.as_str() < b.as_ref().unwrap() {
-    return Ok(i);
+  if a.is_none() {
+    let mut c = key_between_valid(alphabet, a, b)?;
+
+    let mut result: Vec<Vec<u8>> = Vec::with_capacity(n);
+    result.push(c.to_owned());
+    for _i in 0..(n - 1) {
+      c = key_between_valid(alphabet, a, Some(&c))?;
+      result.push(c.to_owned());
+    }
+    result.reverse();
+    return Ok(result);
   }
-  Ok(int_a + &midpoint(float_part_a, ""))
+  let mid = n / 2;
+  let c = key_between_valid(alphabet, a, b)?;
+
+  let mut result: Vec<Vec<u8>> = Vec::with_capacity(n);
+  result.extend(n_keys_between_valid(alphabet, a, Some(&c), mid)?);
+  let rest = n_keys_between_valid(alphabet, Some(&c), b, n - mid - 1)?;
+  result.push(c);
+  result.extend(rest);
+  Ok(result)
+}
+
+/// keys of ASCII alphabets are always valid UTF-8
+#[cfg(feature = "alloc")]
+fn into_string(key: Vec<u8>) -> String {
+  String::from_utf8(key).expect("alphabet is ASCII")
 }
 
 /// `a < b` lexicographically if `b` is non-empty.
 /// a == "" means first possible string.
 /// b == "" means last possible string.
 /// a, b MUST be str without head
-fn midpoint(a: &str, b: &str) -> String {
-  if !b.is_empty() {
-    // remove longest common prefix.  pad `a` with 0s as we
-    // go.  note that we don't need to pad `b`, because it can't
-    // end before `a` while traversing the common prefix.
-    let mut i = 0;
-    for _ in 0..a.len() {
-      let mut c: char = '0';
-      if a.len() > i {
-        c = a.chars().nth(i).unwrap()
-      }
-      if i >= b.len() || c != b.chars().nth(i).unwrap() {
-        break;
-      }
-      i += 1;
-    }
-    if i > 0 {
-      if i as i64 > a.len() as i64 - 1 {
-        return b[0..i].to_string() + &midpoint("", &b[i..]);
-      } else {
-        return b[0..i].to_string() + &midpoint(&a[i..], &b[i..]);
-      }
+/// the result is appended to out.
+fn midpoint<B: KeyBuf + ?Sized>(alphabet: &Alphabet, mut a: &[u8], mut b: &[u8], out: &mut B) -> Result<(), Error> {
+  loop {
+    // remove longest common prefix. `a` never needs padding with 0s,
+    // because `b` can't end before `a` while traversing the common prefix.
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    out.extend_from_slice(&b[..prefix])?;
+    a = &a[prefix..];
+    b = &b[prefix..];
+
+    // first digits (or lack of digit) are different
+    let digit_a = a.first().map_or(0, |d| alphabet.digit_value(*d).unwrap());
+    let digit_b = b
+      .first()
+      .map_or(alphabet.radix(), |d| alphabet.digit_value(*d).unwrap());
+    if digit_b - digit_a > 1 {
+      return out.push(alphabet.digit((digit_a + digit_b).div_ceil(2)));
     }
-  }
-
-  // first digits (or lack of digit) are different
-  let mut digit_a: usize = 0;
-  if !a.is_empty() {
-    digit_a = BASE62_DIGITS.find(a.chars().next().unwrap()).unwrap()
-  }
-  let mut digit_b = BASE62_DIGITS.len();
-  if !b.is_empty() {
-    digit_b = BASE62_DIGITS.find(b.chars().next().unwrap()).unwrap()
-  }
-  if digit_b - digit_a > 1 {
-    let mid_digit = (0.5 * (digit_a + digit_b) as f64).round() as usize;
-    return BASE62_DIGITS.chars().nth(mid_digit).unwrap().to_string();
-  }
 
-  // first digits are consecutive
-  if b.len() > 1 {
-    if !b.starts_with('0') {
-      return b[0..1].to_string();
+    // first digits are consecutive
+    if b.len() > 1 {
+      if b[0] != alphabet.zero_digit() {
+        return out.push(b[0]);
+      }
+      out.push(alphabet.digit(digit_a))?;
+      a = &[];
+      b = &b[1..];
+      continue;
     }
-    return BASE62_DIGITS.chars().nth(digit_a).unwrap().to_string() + &midpoint("", &b[1..]);
-  }
 
-  // `b` is empty or has length 1 (a single digit).
-  // the first digit of `a` is the previous digit to `b`,
-  // or 9 if `b` is null.
-  // given, for example, midpoint('49', '5'), return
-  // '4' + midpoint('9', null), which will become
-  // '4' + '9' + midpoint('', null), which is '495'
-  let mut sa = "";
-  if !a.is_empty() {
-    sa = &a[1..]
+    // `b` is empty or has length 1 (a single digit).
+    // the first digit of `a` is the previous digit to `b`,
+    // or 9 if `b` is null.
+    // given, for example, midpoint('49', '5'), return
+    // '4' + midpoint('9', null), which will become
+    // '4' + '9' + midpoint('', null), which is '495'
+    out.push(alphabet.digit(digit_a))?;
+    a = a.get(1..).unwrap_or_default();
+    b = &[];
   }
-  return BASE62_DIGITS.chars().nth(digit_a).unwrap().to_string() + &midpoint(sa, "");
 }
 
-fn validate_int(i: &str) -> Result<(), String> {
-  let exp = get_int_len(i.chars().next().unwrap())?;
+fn validate_int(alphabet: &Alphabet, i: &[u8]) -> Result<(), Error> {
+  let exp = match alphabet.int_len(i[0]) {
+    Some(exp) => exp,
+    None => return Err(Error::InvalidHead { key: key_string(i) }),
+  };
 
   if i.len() != exp {
-    return Err(format!("invalid integer part of order key: {}", i));
+    return Err(Error::TruncatedInteger {
+      key: key_string(i),
+      expected_len: exp,
+    });
   }
   Ok(())
 }
 
 /**
- * length map:
- * A-Z -> 28-2
- * a-z -> 2-28
+ * throw error when shorter than `alphabet.int_len(head)`
  */
-fn get_int_len(head: char) -> Result<usize, String> {
-  if ('a'..='z').contains(&head) {
-    Ok((head as usize - 'a' as usize + 2) as usize)
-  } else if ('A'..='Z').contains(&head) {
-    Ok(('Z' as usize - head as usize + 2) as usize)
-  } else {
-    Err(format!("invalid order key head: {}", head))
-  }
-}
-
-/**
- * throw error when shorter than `get_int_len(head)`
- */
-fn get_int_part(key: &str) -> Result<String, String> {
-  let int_part_len = get_int_len(key.chars().next().unwrap())?;
+fn get_int_part<'a>(alphabet: &Alphabet, key: &'a [u8]) -> Result<&'a [u8], Error> {
+  let int_part_len = match alphabet.int_len(key[0]) {
+    Some(len) => len,
+    None => return Err(Error::InvalidHead { key: key_string(key) }),
+  };
 
   if int_part_len > key.len() {
-    return Err(format!("invalid order key: {}", key));
+    return Err(Error::TruncatedInteger {
+      key: key_string(key),
+      expected_len: int_part_len,
+    });
   }
-  Ok(key[0..int_part_len].to_string())
+  Ok(&key[0..int_part_len])
 }
 
 /**
  * throw when:
+ * key is empty
  * first charater is not valid head
- * short than `get_int_len(head)`
+ * short than `alphabet.int_len(head)`
+ * any other character is not a digit
  * ends with 0
  */
-fn validate_order_key(key: &str) -> Result<(), String> {
-  if key == SMALLEST_INT {
-    return Err(format!("invalid order key: {}", key));
+fn validate_order_key(alphabet: &Alphabet, key: &[u8]) -> Result<(), Error> {
+  if key.is_empty() {
+    return Err(Error::EmptyKey);
+  }
+  if alphabet.is_smallest_int(key) {
+    return Err(Error::SmallestKey { key: key_string(key) });
   }
   // get_int_part will return error if the first character is bad,
   // or the key is too short.  we'd call it to check these things
   // even if we didn't need the result
-  let int_part = get_int_part(key)?;
+  let int_part = get_int_part(alphabet, key)?;
 
+  // every other function relies on this, digits are looked up without checks
+  if let Some(position) = (1..key.len()).find(|i| alphabet.digit_value(key[*i]).is_none()) {
+    return Err(Error::InvalidDigit {
+      key: key_string(key),
+      position,
+    });
+  }
   let float_part = &key[int_part.len()..];
-  if float_part.ends_with('0') {
-    return Err(format!("invalid order key: {}", key));
+  if float_part.last() == Some(&alphabet.zero_digit()) {
+    return Err(Error::TrailingZero { key: key_string(key) });
   }
   Ok(())
 }
 
-/// returns error if x is invalid, or if range is exceeded
+/// appends x + 1 to out, returns false (appending nothing) if range is exceeded
+/// returns error if x is invalid
 /// x MUST be int without float part
-fn increment_int(x: &str) -> Result<String, String> {
-  validate_int(x)?;
-
-  let mut digs: Vec<char> = x.chars().collect();
-  let head = digs[0];
-  digs.remove(0);
-  let mut carry = true;
-
-  let mut i = digs.len() as i64 - 1;
-  while carry && i >= 0 {
-    let d = BASE62_DIGITS.find(digs[i as usize]).unwrap() + 1;
-    if d == BASE62_DIGITS.len() {
-      digs[i as usize] = '0';
-    } else {
-      digs[i as usize] = BASE62_DIGITS.chars().nth(d).unwrap();
-      carry = false;
+fn increment_int<B: KeyBuf + ?Sized>(alphabet: &Alphabet, x: &[u8], out: &mut B) -> Result<bool, Error> {
+  validate_int(alphabet, x)?;
+
+  let head = x[0];
+  let digs = &x[1..];
+
+  // the last digit below the max digit takes the carry, digits after it wrap to 0
+  if let Some(i) = digs.iter().rposition(|d| *d != alphabet.max_digit()) {
+    out.push(head)?;
+    out.extend_from_slice(&digs[..i])?;
+    out.push(alphabet.digit(alphabet.digit_value(digs[i]).unwrap() + 1))?;
+    for _ in i + 1..digs.len() {
+      out.push(alphabet.zero_digit())?;
     }
+    return Ok(true);
+  }
 
-    i -= 1;
+  let half = alphabet.half();
+  let head_index = alphabet.head_index(head).unwrap();
+  if head_index == half - 1 {
+    out.push(alphabet.head(half))?;
+    out.push(alphabet.zero_digit())?;
+    return Ok(true);
   }
-  if carry {
-    if head == 'Z' {
-      return Ok("a0".to_owned());
-    }
-    if head == 'z' {
-      return Ok("".to_owned());
-    }
-    let h = (head as u8 + 1) as char;
-    if h > 'a' {
-      // a-z -> incr
-      digs.push('0')
-    } else {
-      // A-Z -> decr
-      digs.pop();
-    }
-    return Ok(h.to_string() + &digs.iter().collect::<String>());
+  if head_index == 2 * half - 1 {
+    return Ok(false);
   }
-  Ok(head.to_string() + &digs.iter().collect::<String>())
+  let h = head_index + 1;
+  let len = if h > half {
+    // positive -> incr
+    digs.len() + 1
+  } else {
+    // negative -> decr
+    digs.len() - 1
+  };
+  out.push(alphabet.head(h))?;
+  for _ in 0..len {
+    out.push(alphabet.zero_digit())?;
+  }
+  Ok(true)
 }
 
-fn decrement_int(x: &str) -> Result<String, String> {
-  validate_int(x)?;
-
-  let mut digs: Vec<char> = x.chars().collect();
+/// appends x - 1 to out, returns false (appending nothing) if range is exceeded
+/// returns error if x is invalid
+/// x MUST be int without float part
+fn decrement_int<B: KeyBuf + ?Sized>(alphabet: &Alphabet, x: &[u8], out: &mut B) -> Result<bool, Error> {
+  validate_int(alphabet, x)?;
+
+  let head = x[0];
+  let digs = &x[1..];
+
+  // the last digit above 0 takes the borrow, digits after it wrap to the max digit
+  if let Some(i) = digs.iter().rposition(|d| *d != alphabet.zero_digit()) {
+    out.push(head)?;
+    out.extend_from_slice(&digs[..i])?;
+    out.push(alphabet.digit(alphabet.digit_value(digs[i]).unwrap() - 1))?;
+    for _ in i + 1..digs.len() {
+      out.push(alphabet.max_digit())?;
+    }
+    return Ok(true);
+  }
 
-  let head = digs[0];
-  digs.remove(0);
-  let mut borrow = true;
+  let half = alphabet.half();
+  let head_index = alphabet.head_index(head).unwrap();
+  if head_index == half {
+    out.push(alphabet.head(half - 1))?;
+    out.push(alphabet.max_digit())?;
+    return Ok(true);
+  }
+  if head_index == 0 {
+    return Ok(false);
+  }
+  let h = head_index - 1;
+  let len = if h < half - 1 { digs.len() + 1 } else { digs.len() - 1 };
+  out.push(alphabet.head(h))?;
+  for _ in 0..len {
+    out.push(alphabet.max_digit())?;
+  }
+  Ok(true)
+}
 
-  let mut i = digs.len() as i64 - 1;
-  while borrow && i >= 0 {
-    let d: i64 = BASE62_DIGITS.find(digs[i as usize]).unwrap() as i64 - 1;
+/// same as float64_approx, but key MUST already be validated.
+#[cfg(feature = "std")]
+fn float64_approx_valid(alphabet: &Alphabet, key: &[u8]) -> Result<f64, Error> {
+  let ip = get_int_part(alphabet, key)?;
 
-    if d == -1 {
-      digs[i as usize] = BASE62_DIGITS.chars().nth_back(0).unwrap();
-    } else {
-      digs[i as usize] = BASE62_DIGITS.chars().nth(d as usize).unwrap();
-      borrow = false
-    }
-    i -= 1;
+  let head = ip[0];
+  let digs = &ip[1..];
+  let base = alphabet.radix() as f64;
+  let mut rv: f64 = 0.0;
+  for i in 0..digs.len() {
+    let d = digs[digs.len() - i - 1];
+    let p = match alphabet.digit_value(d) {
+      Some(p) => p,
+      None => {
+        return Err(Error::InvalidDigit {
+          key: key_string(key),
+          position: digs.len() - i,
+        })
+      }
+    };
+    rv += base.powf(i as f64) * p as f64
+  }
+
+  let fp = &key[ip.len()..];
+  for (i, d) in fp.iter().enumerate() {
+    let p = match alphabet.digit_value(*d) {
+      Some(p) => p,
+      None => {
+        return Err(Error::InvalidDigit {
+          key: key_string(key),
+          position: ip.len() + i,
+        })
+      }
+    };
+    rv += (p as f64) / base.powf((i + 1) as f64)
   }
 
-  if borrow {
-    if head == 'a' {
-      return Ok("Z".to_owned() + &BASE62_DIGITS.chars().nth_back(0).unwrap().to_string());
-    }
-    if head == 'A' {
-      return Ok("".to_owned());
-    }
-    let h: char = (head as u8 - 1) as char;
-    if h < 'Z' {
-      digs.push(BASE62_DIGITS.chars().nth_back(0).unwrap());
-    } else {
-      digs.pop();
-    }
-    return Ok(h.to_string() + &digs.iter().collect::<String>());
+  if alphabet.is_negative_head(head) {
+    rv *= -1.0;
   }
 
-  Ok(head.to_string() + &digs.iter().collect::<String>())
+  Ok(rv)
 }
 
 /// float64_approx converts a key as generated by key_between() to a float64.
 /// Because the range of keys is far larger than float64 can represent
 /// accurately, this is necessarily approximate. But for many use cases it should
-/// be, as they say, close enough for jazz.
-pub fn float64_approx(key: &str) -> Result<f64, String> {
-  if key.is_empty() {
-    return Err("invalid order key".to_string());
-  }
-
-  validate_order_key(key)?;
+/// be, as they say, close enough for jazz. Keys convert in order only when they share
+/// a positive head, see `Alphabet::float64_approx`.
+#[cfg(feature = "std")]
+pub fn float64_approx(key: &str) -> Result<f64, Error> {
+  Alphabet::BASE62.float64_approx(key)
+}
 
-  let ip = get_int_part(key)?;
+/// to_rational returns the exact value of key. See `Alphabet::to_rational`.
+#[cfg(feature = "rational")]
+pub fn to_rational(key: &str) -> Result<num_rational::BigRational, Error> {
+  Alphabet::BASE62.to_rational(key)
+}
 
-  let mut digs: Vec<char> = ip.chars().collect();
-  let head = digs[0];
-  digs.remove(0);
-  let mut rv: f64 = 0.0;
-  for i in 0..digs.len() {
-    let d = digs[digs.len() - i - 1];
-    let p = BASE62_DIGITS.find(d);
-    if p == None {
-      return Err(format!("invalid order key: {}", key));
-    }
-    rv += (BASE62_DIGITS.len() as f64).powf(i as f64) * p.unwrap() as f64
-  }
+/// from_rational returns the key whose value is exactly value. See `Alphabet::from_rational`.
+#[cfg(feature = "rational")]
+pub fn from_rational(value: &num_rational::BigRational) -> Result<String, Error> {
+  Alphabet::BASE62.from_rational(value)
+}
 
-  let fp = key[ip.len()..].to_owned();
-  for (i, d) in fp.chars().enumerate() {
-    let p = BASE62_DIGITS.find(d as char);
-    if p == None {
-      return Err(format!("invalid key: {}", key));
-    }
-    rv += (p.unwrap() as f64) / (BASE62_DIGITS.len() as f64).powf((i + 1) as f64)
-  }
+/// to_f64 returns the f64 nearest to the value of key. See `Alphabet::to_f64`.
+#[cfg(feature = "rational")]
+pub fn to_f64(key: &str) -> Result<f64, Error> {
+  Alphabet::BASE62.to_f64(key)
+}
 
-  if head < 'a' {
-    rv *= -1.0;
-  }
+/// from_f64 returns the shortest key that to_f64 converts back to value. See `Alphabet::from_f64`.
+#[cfg(feature = "rational")]
+pub fn from_f64(value: f64) -> Result<String, Error> {
+  Alphabet::BASE62.from_f64(value)
+}
 
-  Ok(rv)
+/// migrate_positions returns keys sorting as legacy numeric positions do, equal
+/// positions in the order they appear. See `Alphabet::migrate_positions`.
+#[cfg(feature = "alloc")]
+pub fn migrate_positions<P: PartialOrd>(positions: &[P]) -> Result<Vec<String>, Error> {
+  Alphabet::BASE62.migrate_positions(positions)
 }
 
 /// n_keys_between returns n keys between a and b that sorts lexicographically.
 /// Either a or b can be empty strings. If a is empty it indicates smallest key,
 /// If b is empty it indicates largest key.
 /// b must be empty string or > a.
-pub fn n_keys_between(a: &Option<String>, b: &Option<String>, n: usize) -> Result<Vec<String>, String> {
-  if n == 0 {
-    return Ok(vec![]);
-  }
-  if n == 1 {
-    let c = key_between(a, b)?;
+#[cfg(feature = "alloc")]
+pub fn n_keys_between(a: &Option<String>, b: &Option<String>, n: usize) -> Result<Vec<String>, Error> {
+  Alphabet::BASE62.n_keys_between(a, b, n)
+}
 
-    return Ok(vec![c]);
-  }
-  if b.is_none() {
-    let mut c = key_between(a, b)?;
-    let mut result: Vec<String> = Vec::with_capacity(n);
-    result.push(c.to_owned());
+/// key_between_unchecked returns a key that sorts lexicographically between a and b,
+/// without validating them. a and b MUST be valid keys and a < b.
+/// See `Alphabet::key_between_unchecked`.
+#[cfg(feature = "alloc")]
+pub fn key_between_unchecked(a: Option<&str>, b: Option<&str>) -> Result<String, Error> {
+  Alphabet::BASE62.key_between_unchecked(a, b)
+}
 
-    for _i in 0..((n as usize) - 1) {
-      c = key_between(&Some(c), b)?;
-      result.push(c.to_owned());
-    }
+/// n_keys_between_unchecked returns n keys between a and b that sorts lexicographically,
+/// without validating them. a and b MUST be valid keys and a < b.
+/// See `Alphabet::key_between_unchecked`.
+#[cfg(feature = "alloc")]
+pub fn n_keys_between_unchecked(a: Option<&str>, b: Option<&str>, n: usize) -> Result<Vec<String>, Error> {
+  Alphabet::BASE62.n_keys_between_unchecked(a, b, n)
+}
 
-    return Ok(result);
-  }
-  if a.is_none() {
-    let mut c = key_between(a, b)?;
+/// n_keys_between_balanced returns n keys between a and b that sorts lexicographically,
+/// evenly spaced and as short as possible. See `Alphabet::n_keys_between_balanced`.
+#[cfg(feature = "alloc")]
+pub fn n_keys_between_balanced(a: &Option<String>, b: &Option<String>, n: usize) -> Result<Vec<String>, Error> {
+  Alphabet::BASE62.n_keys_between_balanced(a, b, n)
+}
 
-    let mut result: Vec<String> = Vec::with_capacity(n);
-    result.push(c.to_owned());
-    for _i in 0..(n as usize - 1) {
-      c = key_between(a, &Some(c))?;
-      result.push(c.to_owned());
-    }
-    result.reverse();
-    return Ok(result);
-  }
-  let mid = n / 2;
-  let c = key_between(a, b)?;
+/// rebalance returns a fresh set of short keys for sorted keys, in the same order.
+/// See `Alphabet::rebalance`.
+#[cfg(feature = "alloc")]
+pub fn rebalance<K: AsRef<str>>(keys: &[K]) -> Result<Vec<String>, Error> {
+  Alphabet::BASE62.rebalance(keys)
+}
 
-  let mut result: Vec<String> = Vec::with_capacity(n);
-  {
-    let key_r = n_keys_between(a, &Some(c.to_owned()), mid)?;
-    for item in key_r {
-      result.push(item.clone());
-    }
-  }
-  result.push(c.to_owned());
-  {
-    let key_r = n_keys_between(&Some(c), b, n - mid - 1)?;
-    for item in key_r.iter() {
-      result.push(item.to_owned());
-    }
-  }
-  Ok(result)
+/// rebalance_window returns the smallest range of keys to rewrite so that no key is
+/// longer than max_len, and their new keys. See `Alphabet::rebalance_window`.
+#[cfg(feature = "alloc")]
+pub fn rebalance_window<K: AsRef<str>>(keys: &[K], max_len: usize) -> Result<(Range<usize>, Vec<String>), Error> {
+  Alphabet::BASE62.rebalance_window(keys, max_len)
+}
+
+/// analyze reports the length distribution and headroom of sorted keys, and the
+/// windows to rebalance so that no key is longer than max_len. See `Alphabet::analyze`.
+#[cfg(feature = "alloc")]
+pub fn analyze<K: AsRef<str>>(keys: &[K], max_len: usize) -> Result<KeyStats, Error> {
+  Alphabet::BASE62.analyze(keys, max_len)
+}
+
+/// key_for_move returns the new key of the item at index from of sorted keys,
+/// so that it moves to index to. See `Alphabet::key_for_move`.
+#[cfg(feature = "alloc")]
+pub fn key_for_move<K: AsRef<str>>(keys: &[K], from: usize, to: usize) -> Result<String, Error> {
+  Alphabet::BASE62.key_for_move(keys, from, to)
+}
+
+/// keys_for_block_move returns the new keys of the items in block of sorted keys,
+/// so that the block moves to index to. See `Alphabet::keys_for_block_move`.
+#[cfg(feature = "alloc")]
+pub fn keys_for_block_move<K: AsRef<str>>(keys: &[K], block: Range<usize>, to: usize) -> Result<Vec<String>, Error> {
+  Alphabet::BASE62.keys_for_block_move(keys, block, to)
+}
+
+/// n_keys_between_jittered returns n random keys between a and b that sorts lexicographically,
+/// each drawn with `bits` random bits from rng. See `Alphabet::n_keys_between_jittered`.
+#[cfg(feature = "alloc")]
+pub fn n_keys_between_jittered<R: RandomSource + ?Sized>(
+  a: &Option<String>,
+  b: &Option<String>,
+  n: usize,
+  rng: &mut R,
+  bits: u32,
+) -> Result<Vec<String>, Error> {
+  Alphabet::BASE62.n_keys_between_jittered(a, b, n, rng, bits)
 }
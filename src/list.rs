@@ -0,0 +1,159 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::{Error, FractionalIndex};
+
+/// ElementId identifies an element of a `FractionalList`. It stays the same
+/// when the element moves, and is never reused by the list that issued it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElementId(u64);
+
+impl ElementId {
+  pub fn get(self) -> u64 {
+    self.0
+  }
+}
+
+/// FractionalList is an ordered list whose elements are sorted by a fractional
+/// index, so inserting or moving an element changes the key of that element only.
+/// `key` gives the key to persist next to the element.
+#[derive(Clone, Debug)]
+pub struct FractionalList<T> {
+  order: BTreeMap<FractionalIndex, ElementId>,
+  elements: HashMap<ElementId, (FractionalIndex, T)>,
+  next_id: u64,
+}
+
+impl<T> FractionalList<T> {
+  pub fn new() -> Self {
+    FractionalList {
+      order: BTreeMap::new(),
+      elements: HashMap::new(),
+      next_id: 0,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.order.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.order.is_empty()
+  }
+
+  pub fn contains(&self, id: ElementId) -> bool {
+    self.elements.contains_key(&id)
+  }
+
+  pub fn get(&self, id: ElementId) -> Option<&T> {
+    self.elements.get(&id).map(|(_, value)| value)
+  }
+
+  pub fn get_mut(&mut self, id: ElementId) -> Option<&mut T> {
+    self.elements.get_mut(&id).map(|(_, value)| value)
+  }
+
+  /// key returns the fractional index the element is currently sorted by.
+  pub fn key(&self, id: ElementId) -> Option<&FractionalIndex> {
+    self.elements.get(&id).map(|(key, _)| key)
+  }
+
+  /// push_front inserts value before every element.
+  pub fn push_front(&mut self, value: T) -> Result<ElementId, Error> {
+    let key = key_between(None, self.order.keys().next())?;
+    Ok(self.insert_key(key, value))
+  }
+
+  /// push_back inserts value after every element.
+  pub fn push_back(&mut self, value: T) -> Result<ElementId, Error> {
+    let key = key_between(self.order.keys().next_back(), None)?;
+    Ok(self.insert_key(key, value))
+  }
+
+  /// insert_before inserts value right before the element id.
+  pub fn insert_before(&mut self, id: ElementId, value: T) -> Result<ElementId, Error> {
+    let next = self.key_of(id)?;
+    let prev = self.order.range(..next).next_back().map(|(key, _)| key);
+    let key = key_between(prev, Some(next))?;
+    Ok(self.insert_key(key, value))
+  }
+
+  /// insert_after inserts value right after the element id.
+  pub fn insert_after(&mut self, id: ElementId, value: T) -> Result<ElementId, Error> {
+    let prev = self.key_of(id)?;
+    let next = self.order.range(prev..).nth(1).map(|(key, _)| key);
+    let key = key_between(Some(prev), next)?;
+    Ok(self.insert_key(key, value))
+  }
+
+  /// move_to moves the element id so that it is at index in the list,
+  /// giving it a new key. Finding the neighbours at index takes O(index).
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index >= len`.
+  pub fn move_to(&mut self, id: ElementId, index: usize) -> Result<(), Error> {
+    let old = self.key_of(id)?.to_owned();
+    assert!(
+      index < self.len(),
+      "index {} out of bounds, len is {}",
+      index,
+      self.len()
+    );
+
+    self.order.remove(&old);
+    let mut others = self.order.keys();
+    let prev = index.checked_sub(1).and_then(|i| others.nth(i));
+    let next = others.next();
+    let key = match key_between(prev, next) {
+      Ok(key) => key,
+      Err(e) => {
+        self.order.insert(old, id);
+        return Err(e);
+      }
+    };
+    self.order.insert(key.to_owned(), id);
+    self.elements.get_mut(&id).unwrap().0 = key;
+    Ok(())
+  }
+
+  /// remove removes the element id and returns its value.
+  pub fn remove(&mut self, id: ElementId) -> Option<T> {
+    let (key, value) = self.elements.remove(&id)?;
+    self.order.remove(&key);
+    Some(value)
+  }
+
+  /// iter visits the elements in order.
+  pub fn iter(&self) -> impl Iterator<Item = (ElementId, &T)> + '_ {
+    self.order.values().map(move |id| (*id, &self.elements[id].1))
+  }
+
+  fn key_of(&self, id: ElementId) -> Result<&FractionalIndex, Error> {
+    self.key(id).ok_or(Error::UnknownId { id: id.0 })
+  }
+
+  fn insert_key(&mut self, key: FractionalIndex, value: T) -> ElementId {
+    let id = ElementId(self.next_id);
+    self.next_id += 1;
+    self.order.insert(key.to_owned(), id);
+    self.elements.insert(id, (key, value));
+    id
+  }
+}
+
+fn key_between(a: Option<&FractionalIndex>, b: Option<&FractionalIndex>) -> Result<FractionalIndex, Error> {
+  match (a, b) {
+    (None, None) => Ok(FractionalIndex::default()),
+    (Some(a), None) => a.after(),
+    (None, Some(b)) => b.before(),
+    (Some(a), Some(b)) => a.between(b),
+  }
+}
+
+impl<T> Default for FractionalList<T> {
+  fn default() -> Self {
+    FractionalList::new()
+  }
+}
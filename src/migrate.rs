@@ -0,0 +1,32 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{into_string, n_keys_between_valid, Alphabet, Error};
+
+impl Alphabet {
+  /// migrate_positions returns a key for every legacy numeric position, e.g. of a
+  /// `position INTEGER` or `position REAL` column, the key at index i for positions[i].
+  /// Keys sort as their positions do. Equal positions get keys in the order they
+  /// appear in positions, so the same positions always give the same keys.
+  /// The keys are those of `n_keys_between` with both bounds empty, `a0`, `a1`, ...,
+  /// only the order of the positions matters, not their values.
+  /// Fails with `Error::Unrepresentable` if a position can't be compared, like NaN.
+  pub fn migrate_positions<P: PartialOrd>(&self, positions: &[P]) -> Result<Vec<String>, Error> {
+    if positions.iter().any(|p| p.partial_cmp(p).is_none()) {
+      return Err(Error::Unrepresentable);
+    }
+    // stable, ties keep their order
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by(|a, b| positions[*a].partial_cmp(&positions[*b]).unwrap_or(Ordering::Equal));
+
+    let mut keys = alloc::vec![String::new(); positions.len()];
+    for (i, key) in order
+      .into_iter()
+      .zip(n_keys_between_valid(self, None, None, positions.len())?)
+    {
+      keys[i] = into_string(key);
+    }
+    Ok(keys)
+  }
+}
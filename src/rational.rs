@@ -0,0 +1,162 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::{get_int_part, into_string, validate_order_key, Alphabet, Error};
+
+impl Alphabet {
+  /// to_rational returns the exact value of key. Unlike `float64_approx`, which
+  /// follows the Go implementation, integers are numbered consecutively across heads:
+  /// `a0` is 0, `az` 61, `b00` 62, `Zz` -1 and `Z0` -62. Each fractional digit is one
+  /// more place in the radix, `a0V` is 1/2. Keys sort as their values do.
+  pub fn to_rational(&self, key: &str) -> Result<BigRational, Error> {
+    validate_order_key(self, key.as_bytes())?;
+    to_rational_valid(self, key.as_bytes())
+  }
+
+  /// from_rational returns the key whose value is exactly value, see `to_rational`.
+  /// Fails with `Error::Unrepresentable` when value has no finite expansion in the
+  /// radix, like 1/3 in base62, and with `Error::RangeOverflow` or `Error::RangeUnderflow`
+  /// beyond the largest integer, or at and below the smallest one.
+  pub fn from_rational(&self, value: &BigRational) -> Result<String, Error> {
+    check_range(self, value)?;
+    // the denominator must divide a power of the radix
+    let radix = BigInt::from(self.radix());
+    let mut rest = value.denom().clone();
+    loop {
+      let common = rest.gcd(&radix);
+      if common.is_one() {
+        break;
+      }
+      rest /= common;
+    }
+    if !rest.is_one() {
+      return Err(Error::Unrepresentable);
+    }
+
+    let int = value.floor();
+    let mut key = int_key(self, int.numer());
+    let mut fraction = value - int;
+    while !fraction.is_zero() {
+      fraction *= &radix;
+      let digit = fraction.to_integer();
+      fraction -= &digit;
+      key.push(self.digit(digit.to_usize().unwrap_or_default()));
+    }
+    Ok(into_string(key))
+  }
+
+  /// to_f64 returns the f64 nearest to the value of key, see `to_rational`.
+  /// Unlike `float64_approx`, it is rounded correctly and keeps the order of keys,
+  /// but much slower.
+  pub fn to_f64(&self, key: &str) -> Result<f64, Error> {
+    self.to_rational(key)?.to_f64().ok_or(Error::Unrepresentable)
+  }
+
+  /// from_f64 returns the shortest key that `to_f64` converts back to value, of those
+  /// the one closest to value. As `to_f64` keeps the order of keys, float positions
+  /// convert to keys in the same order. Fails with `Error::Unrepresentable` for NaN and
+  /// infinities, and with `Error::RangeOverflow` or `Error::RangeUnderflow` beyond the
+  /// range of keys.
+  pub fn from_f64(&self, value: f64) -> Result<String, Error> {
+    let exact = BigRational::from_float(value).ok_or(Error::Unrepresentable)?;
+    check_range(self, &exact)?;
+    // reals rounding to value: up to halfway to the next floats, included when ties go to value
+    let halfway = |next: f64| (BigRational::from_float(next).unwrap_or_default() + &exact) / BigInt::from(2);
+    let (lo, hi) = (halfway(value.next_down()), halfway(value.next_up()));
+    let inclusive = value.to_bits() & 1 == 0;
+
+    // integers are shortest, with the shortest head closest to zero. Otherwise the
+    // fewest fractional digits, closest to value
+    let radix = BigRational::from_integer(BigInt::from(self.radix()));
+    let mut scale = BigRational::one();
+    let mut target = BigRational::zero();
+    loop {
+      let (lo, hi) = (&lo * &scale, &hi * &scale);
+      let (first, last) = if inclusive {
+        (lo.ceil(), hi.floor())
+      } else {
+        (lo.floor() + BigInt::one(), hi.ceil() - BigInt::one())
+      };
+      if first <= last {
+        let nearest = (&target * &scale).round().clamp(first, last);
+        return self.from_rational(&(nearest / scale));
+      }
+      scale *= &radix;
+      target = exact.clone();
+    }
+  }
+}
+
+/// same as to_rational, but key MUST already be validated.
+fn to_rational_valid(alphabet: &Alphabet, key: &[u8]) -> Result<BigRational, Error> {
+  let int_part = get_int_part(alphabet, key)?;
+  let radix = BigInt::from(alphabet.radix());
+  let digits = |digits: &[u8]| {
+    digits.iter().fold(BigInt::zero(), |value, d| {
+      value * &radix + alphabet.digit_value(*d).unwrap_or_default()
+    })
+  };
+
+  let head_index = alphabet.head_index(key[0]).unwrap_or_default();
+  let half = alphabet.half();
+  let int = if head_index < half {
+    digits(&int_part[1..]) - offset(alphabet, half - head_index)
+  } else {
+    offset(alphabet, head_index - half) + digits(&int_part[1..])
+  };
+
+  let fraction = &key[int_part.len()..];
+  let scale = num_traits::pow(radix.clone(), fraction.len());
+  Ok(BigRational::new(int * &scale + digits(fraction), scale))
+}
+
+/// values from the smallest integer, excluded, to the largest one, included
+fn check_range(alphabet: &Alphabet, value: &BigRational) -> Result<(), Error> {
+  let limit = BigRational::from_integer(offset(alphabet, alphabet.half()));
+  if *value >= limit {
+    return Err(Error::RangeOverflow);
+  }
+  if *value <= -limit {
+    return Err(Error::RangeUnderflow);
+  }
+  Ok(())
+}
+
+/// number of integers of the first count positive heads, or negative ones
+fn offset(alphabet: &Alphabet, count: usize) -> BigInt {
+  let radix = BigInt::from(alphabet.radix());
+  (1..=count).map(|digits| num_traits::pow(radix.clone(), digits)).sum()
+}
+
+/// the integer key of int, which MUST be in the range of the alphabet
+fn int_key(alphabet: &Alphabet, int: &BigInt) -> Vec<u8> {
+  let half = alphabet.half();
+  // rank of the head, counted from zero on both sides
+  let mut rank = 0;
+  let (head, mut value) = if int.is_negative() {
+    while *int < -offset(alphabet, rank + 1) {
+      rank += 1;
+    }
+    (half - 1 - rank, int + offset(alphabet, rank + 1))
+  } else {
+    while *int >= offset(alphabet, rank + 1) {
+      rank += 1;
+    }
+    (half + rank, int - offset(alphabet, rank))
+  };
+
+  let radix = BigInt::from(alphabet.radix());
+  let mut key = alloc::vec![alphabet.zero_digit(); rank + 2];
+  key[0] = alphabet.head(head);
+  for d in key[1..].iter_mut().rev() {
+    let (rest, digit) = value.div_rem(&radix);
+    *d = alphabet.digit(digit.to_usize().unwrap_or_default());
+    value = rest;
+  }
+  key
+}
@@ -0,0 +1,84 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::balanced::n_keys_balanced_valid;
+use crate::{into_string, key_string, validate_order_key, Alphabet, Error};
+
+impl Alphabet {
+  /// rebalance returns a fresh set of short keys for sorted keys, the key at index i
+  /// replacing keys[i]. The new keys are evenly spaced like `n_keys_between_balanced`
+  /// with both bounds empty, so they don't depend on the old keys but their number.
+  /// keys must be valid and strictly ascending.
+  pub fn rebalance<K: AsRef<str>>(&self, keys: &[K]) -> Result<Vec<String>, Error> {
+    validate_sorted(self, keys)?;
+    let keys = n_keys_balanced_valid(self, None, None, keys.len())?;
+    Ok(keys.into_iter().map(into_string).collect())
+  }
+
+  /// rebalance_window rewrites only the keys around those longer than max_len.
+  /// It returns a range of keys and their replacements, which sort between the keys
+  /// before and after the range. The range is the smallest window found, growing on
+  /// both sides, whose new keys are at most max_len long; when there is none, every
+  /// key is rewritten as by `rebalance`. The range is empty when no key is too long.
+  /// keys must be valid and strictly ascending.
+  pub fn rebalance_window<K: AsRef<str>>(
+    &self,
+    keys: &[K],
+    max_len: usize,
+  ) -> Result<(Range<usize>, Vec<String>), Error> {
+    validate_sorted(self, keys)?;
+    let too_long = |key: &K| key.as_ref().len() > max_len;
+    let (first, last) = match (keys.iter().position(too_long), keys.iter().rposition(too_long)) {
+      (Some(first), Some(last)) => (first, last + 1),
+      _ => return Ok((0..0, vec![])),
+    };
+
+    let (range, window) = grow_window(self, keys, first..last, max_len)?;
+    Ok((range, window.into_iter().map(into_string).collect()))
+  }
+}
+
+/// grows window on both sides until its keys, rewritten between the keys around it,
+/// are at most max_len long, or it covers all keys. keys MUST already be validated.
+pub(crate) fn grow_window<K: AsRef<str>>(
+  alphabet: &Alphabet,
+  keys: &[K],
+  window: Range<usize>,
+  max_len: usize,
+) -> Result<(Range<usize>, Vec<Vec<u8>>), Error> {
+  let (first, last) = (window.start, window.end);
+  let (mut start, mut end) = (first, last);
+  loop {
+    let a = start.checked_sub(1).map(|i| keys[i].as_ref().as_bytes());
+    let b = keys.get(end).map(|key| key.as_ref().as_bytes());
+    let window = n_keys_balanced_valid(alphabet, a, b, end - start)?;
+    if window.iter().all(|key| key.len() <= max_len) || (start == 0 && end == keys.len()) {
+      return Ok((start..end, window));
+    }
+    // grow the side that grew least, or the only one left
+    if start > 0 && (first - start <= end - last || end == keys.len()) {
+      start -= 1;
+    } else {
+      end += 1;
+    }
+  }
+}
+
+/// validates every key and their order
+pub(crate) fn validate_sorted<K: AsRef<str>>(alphabet: &Alphabet, keys: &[K]) -> Result<(), Error> {
+  for key in keys {
+    validate_order_key(alphabet, key.as_ref().as_bytes())?;
+  }
+  for pair in keys.windows(2) {
+    let (a, b) = (pair[0].as_ref().as_bytes(), pair[1].as_ref().as_bytes());
+    if a >= b {
+      return Err(Error::InvalidOrder {
+        a: key_string(a),
+        b: key_string(b),
+      });
+    }
+  }
+  Ok(())
+}
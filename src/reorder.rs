@@ -0,0 +1,76 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::rebalance::validate_sorted;
+use crate::{
+  into_string, key_between_valid, n_keys_between_valid, validate_bounds, validate_order_key, Alphabet, Error,
+};
+
+impl Alphabet {
+  /// key_for_move returns the new key of the item at index from of sorted keys, so that
+  /// it is at index to once sorted again. No other key changes. When from == to,
+  /// the key of the item is validated and returned as it is.
+  /// Only the keys around index to are validated, the others must be sorted.
+  ///
+  /// # Panics
+  ///
+  /// Panics if from or to is out of bounds.
+  pub fn key_for_move<K: AsRef<str>>(&self, keys: &[K], from: usize, to: usize) -> Result<String, Error> {
+    assert!(
+      from < keys.len() && to < keys.len(),
+      "move {} to {} out of bounds",
+      from,
+      to
+    );
+    if from == to {
+      validate_order_key(self, keys[from].as_ref().as_bytes())?;
+      return Ok(keys[from].as_ref().to_owned());
+    }
+    let (a, b) = neighbours(keys, from..from + 1, to);
+    validate_bounds(self, a, b)?;
+    key_between_valid(self, a, b).map(into_string)
+  }
+
+  /// keys_for_block_move returns the new keys of the items in block of sorted keys,
+  /// so that they keep their order and the first one is at index to once sorted again.
+  /// No other key changes. When the block doesn't move, its keys are validated and returned
+  /// as they are.
+  /// Only the keys around the new place of the block are validated, the others must be sorted.
+  ///
+  /// # Panics
+  ///
+  /// Panics if block is out of bounds, or the block doesn't fit at index to.
+  pub fn keys_for_block_move<K: AsRef<str>>(
+    &self,
+    keys: &[K],
+    block: Range<usize>,
+    to: usize,
+  ) -> Result<Vec<String>, Error> {
+    assert!(
+      block.start <= block.end && block.end <= keys.len() && to + block.len() <= keys.len(),
+      "move {:?} to {} out of bounds",
+      block,
+      to
+    );
+    if block.start == to || block.is_empty() {
+      validate_sorted(self, &keys[block.clone()])?;
+      return Ok(keys[block].iter().map(|key| key.as_ref().to_owned()).collect());
+    }
+    let n = block.len();
+    let (a, b) = neighbours(keys, block, to);
+    validate_bounds(self, a, b)?;
+    let keys = n_keys_between_valid(self, a, b, n)?;
+    Ok(keys.into_iter().map(into_string).collect())
+  }
+}
+
+/// keys before and after index to of keys without block
+fn neighbours<K: AsRef<str>>(keys: &[K], block: Range<usize>, to: usize) -> (Option<&[u8]>, Option<&[u8]>) {
+  // index in keys of index i of the remaining keys
+  let original = |i: usize| if i < block.start { i } else { i + block.len() };
+  let a = to.checked_sub(1).map(|i| keys[original(i)].as_ref().as_bytes());
+  let b = keys.get(original(to)).map(|key| key.as_ref().as_bytes());
+  (a, b)
+}
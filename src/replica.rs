@@ -0,0 +1,94 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+  get_int_part, into_string, key_between_valid, key_string, validate_bounds, validate_order_key, Alphabet, Error,
+};
+
+impl Alphabet {
+  /// key_between_with_replica returns a key that sorts lexicographically between a and b
+  /// and ends with the replica id, so replicas inserting between the same neighbours
+  /// get distinct keys. The key is a prefix that sorts between a and b, without being a
+  /// prefix of b, followed by the digits of replica and a last digit counting them.
+  /// Either a or b can be empty strings. If a is empty it indicates smallest key,
+  /// If b is empty it indicates largest key.
+  /// b must be empty string or > a.
+  pub fn key_between_with_replica(
+    &self,
+    a: &Option<String>,
+    b: &Option<String>,
+    replica: u64,
+  ) -> Result<String, Error> {
+    let (a, b) = (a.as_deref().map(str::as_bytes), b.as_deref().map(str::as_bytes));
+    validate_bounds(self, a, b)?;
+    replica_key_valid(self, a, b, replica).map(into_string)
+  }
+
+  /// replica_id returns the replica id of a key generated by key_between_with_replica.
+  /// Keys generated otherwise give an arbitrary id, or an error when their
+  /// fractional part is too short to hold one.
+  pub fn replica_id(&self, key: &str) -> Result<u64, Error> {
+    validate_order_key(self, key.as_bytes())?;
+    replica_id_valid(self, key.as_bytes())
+  }
+}
+
+/// same as key_between_with_replica, but a and b MUST already be validated and ordered.
+pub(crate) fn replica_key_valid(
+  alphabet: &Alphabet,
+  a: Option<&[u8]>,
+  b: Option<&[u8]>,
+  replica: u64,
+) -> Result<Vec<u8>, Error> {
+  let mut digits = vec![];
+  let mut rest = replica;
+  loop {
+    digits.push(alphabet.digit((rest % alphabet.radix() as u64) as usize));
+    rest /= alphabet.radix() as u64;
+    if rest == 0 {
+      break;
+    }
+  }
+  if digits.len() >= alphabet.radix() {
+    return Err(Error::ReplicaTooLarge { replica });
+  }
+
+  // anything appended to a prefix of b would sort after b,
+  // move towards a until the key isn't one
+  let mut key = key_between_valid(alphabet, a, b)?;
+  if let Some(b) = b {
+    while b.starts_with(&key) {
+      key = key_between_valid(alphabet, a, Some(&key))?;
+    }
+  }
+
+  key.extend(digits.iter().rev());
+  key.push(alphabet.digit(digits.len()));
+  Ok(key)
+}
+
+/// same as replica_id, but key MUST already be validated.
+pub(crate) fn replica_id_valid(alphabet: &Alphabet, key: &[u8]) -> Result<u64, Error> {
+  let int_part = get_int_part(alphabet, key)?;
+  let float_part = &key[int_part.len()..];
+
+  let missing = || Error::MissingReplica { key: key_string(key) };
+  let (count, digits) = match float_part.split_last() {
+    Some((count, digits)) => (alphabet.digit_value(*count).ok_or_else(missing)?, digits),
+    None => return Err(missing()),
+  };
+  if count > digits.len() {
+    return Err(missing());
+  }
+
+  let mut replica: u64 = 0;
+  for d in digits[digits.len() - count..].iter() {
+    let d = alphabet.digit_value(*d).ok_or_else(missing)? as u64;
+    replica = replica
+      .checked_mul(alphabet.radix() as u64)
+      .and_then(|r| r.checked_add(d))
+      .ok_or_else(missing)?;
+  }
+  Ok(replica)
+}
@@ -0,0 +1,22 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::convert::TryFrom;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::FractionalIndex;
+
+/// binds as TEXT.
+impl ToSql for FractionalIndex {
+  fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+    Ok(ToSqlOutput::from(self.as_str()))
+  }
+}
+
+/// reads TEXT, rejecting invalid keys.
+impl FromSql for FractionalIndex {
+  fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+    let key = String::column_result(value)?;
+    FractionalIndex::try_from(key).map_err(|e| FromSqlError::Other(Box::new(e)))
+  }
+}
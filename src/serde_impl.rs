@@ -0,0 +1,22 @@
+use alloc::string::String;
+use core::convert::TryFrom;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::FractionalIndex;
+
+/// serializes as the plain key string.
+impl Serialize for FractionalIndex {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
+/// deserializes from a string, rejecting invalid keys.
+impl<'de> Deserialize<'de> for FractionalIndex {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let key = String::deserialize(deserializer)?;
+    FractionalIndex::try_from(key).map_err(D::Error::custom)
+  }
+}
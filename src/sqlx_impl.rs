@@ -0,0 +1,50 @@
+use alloc::string::String;
+use core::convert::TryFrom;
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+
+use crate::FractionalIndex;
+
+/// same SQL type as String, TEXT or VARCHAR.
+impl<DB: Database> Type<DB> for FractionalIndex
+where
+  String: Type<DB>,
+{
+  fn type_info() -> DB::TypeInfo {
+    <String as Type<DB>>::type_info()
+  }
+
+  fn compatible(ty: &DB::TypeInfo) -> bool {
+    <String as Type<DB>>::compatible(ty)
+  }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for FractionalIndex
+where
+  String: Encode<'q, DB>,
+{
+  fn encode(self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+    self.0.encode(buf)
+  }
+
+  fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+    self.0.encode_by_ref(buf)
+  }
+
+  fn size_hint(&self) -> usize {
+    self.0.size_hint()
+  }
+}
+
+/// decodes like String, rejecting invalid keys.
+impl<'r, DB: Database> Decode<'r, DB> for FractionalIndex
+where
+  String: Decode<'r, DB>,
+{
+  fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+    let key = String::decode(value)?;
+    Ok(FractionalIndex::try_from(key)?)
+  }
+}
@@ -0,0 +1,146 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{float64_approx, key_between, n_keys_between, Alphabet, Error};
+
+fn check_insertions(alphabet: &Alphabet) -> Result<(), Error> {
+  // append, prepend, then keep splitting the first gap
+  let mut keys = alphabet.n_keys_between(&None, &None, 50)?;
+  let before = alphabet.n_keys_between(&None, &Some(keys[0].to_owned()), 50)?;
+  keys = before.into_iter().chain(keys).collect();
+  for _ in 0..200 {
+    let next = alphabet.key_between(&Some(keys[0].to_owned()), &Some(keys[1].to_owned()))?;
+    keys.insert(1, next);
+  }
+
+  for pair in keys.windows(2) {
+    assert!(pair[0] < pair[1], "{:?} >= {:?}", pair[0], pair[1]);
+  }
+  for key in keys.iter() {
+    alphabet.validate(key)?;
+    assert!(key
+      .chars()
+      .all(|c| alphabet.digits().contains(c) || alphabet.heads().contains(c)));
+  }
+
+  Ok(())
+}
+
+#[test]
+fn base62_test() -> Result<(), Error> {
+  let base62 = Alphabet::BASE62;
+  assert_eq!(Alphabet::default(), base62);
+
+  let mut left: Option<String> = None;
+  for _ in 0..1000 {
+    let next = base62.key_between(&left, &None)?;
+    assert_eq!(next, key_between(&left, &None)?);
+    left = Some(next);
+  }
+
+  let a = Some("a0".to_owned());
+  let b = Some("a2".to_owned());
+  assert_eq!(base62.n_keys_between(&a, &b, 20)?, n_keys_between(&a, &b, 20)?);
+  assert_eq!(base62.float64_approx("aVV")?, float64_approx("aVV")?);
+
+  check_insertions(&base62)
+}
+
+#[test]
+fn builtin_alphabets_test() -> Result<(), Error> {
+  assert_eq!(Alphabet::BASE36.key_between(&None, &None)?, "i0");
+  assert_eq!(Alphabet::BASE36.key_between(&None, &Some("i0".to_owned()))?, "hz");
+  assert_eq!(Alphabet::HEX.key_between(&None, &None)?, "80");
+  assert_eq!(Alphabet::HEX.key_between(&Some("8f".to_owned()), &None)?, "900");
+  assert_eq!(
+    Alphabet::HEX.key_between(&Some("80".to_owned()), &Some("81".to_owned()))?,
+    "808"
+  );
+  assert_eq!(Alphabet::BASE95.key_between(&None, &None)?, "a ");
+  assert_eq!(
+    Alphabet::BASE95.key_between(&Some("a ".to_owned()), &Some("a!".to_owned()))?,
+    "a P"
+  );
+  assert_eq!(Alphabet::HEX.float64_approx("9108")?, 16.5);
+  assert_eq!(Alphabet::HEX.float64_approx("7f")?, -15.0);
+
+  check_insertions(&Alphabet::BASE95)?;
+  check_insertions(&Alphabet::BASE36)?;
+  check_insertions(&Alphabet::HEX)
+}
+
+#[test]
+fn base36_case_insensitive_test() -> Result<(), Error> {
+  let keys = Alphabet::BASE36.n_keys_between(&None, &Some("i1".to_owned()), 100)?;
+  let mut upper: Vec<String> = keys.iter().map(|k| k.to_uppercase()).collect();
+  upper.sort();
+  assert_eq!(upper, keys.iter().map(|k| k.to_uppercase()).collect::<Vec<_>>());
+
+  Ok(())
+}
+
+#[test]
+fn custom_alphabet_test() -> Result<(), Error> {
+  let binary = Alphabet::new("01", "ABab")?;
+  assert_eq!(binary.key_between(&None, &None)?, "a0");
+  assert_eq!(binary.key_between(&Some("a1".to_owned()), &None)?, "b00");
+  assert_eq!(binary.key_between(&None, &Some("a0".to_owned()))?, "B1");
+  assert_eq!(binary.key_between(&None, &Some("B1".to_owned()))?, "B0");
+  assert_eq!(binary.key_between(&None, &Some("B0".to_owned()))?, "A11");
+  assert_eq!(
+    binary.key_between(&None, &Some("A00".to_owned())),
+    Err(Error::SmallestKey { key: "A00".into() })
+  );
+  assert_eq!(binary.key_between(&Some("b11".to_owned()), &None)?, "b111");
+  assert_eq!(binary.validate("b110"), Err(Error::TrailingZero { key: "b110".into() }));
+  assert_eq!(binary.validate("A00"), Err(Error::SmallestKey { key: "A00".into() }));
+  binary.validate("b111")?;
+
+  let decimal = Alphabet::new("0123456789", "ABCDEFGHIJabcdefghij")?;
+  assert_eq!(decimal.key_between(&Some("a9".to_owned()), &None)?, "b00");
+  check_insertions(&decimal)?;
+
+  // alphabets copy their characters, so they can be built from runtime strings
+  let digits: String = ('0'..='9').collect();
+  let runtime = Alphabet::new(&digits, "ABCDEFGHIJabcdefghij")?;
+  drop(digits);
+  assert_eq!(runtime, decimal);
+  assert_eq!(runtime.digits(), "0123456789");
+
+  assert_eq!(
+    Alphabet::new("0123456789", "BA"),
+    Err(Error::InvalidAlphabet {
+      chars: "BA".into(),
+      position: 1
+    })
+  );
+  assert_eq!(
+    Alphabet::new("0112", "Aa"),
+    Err(Error::InvalidAlphabet {
+      chars: "0112".into(),
+      position: 2
+    })
+  );
+  assert_eq!(
+    Alphabet::new("0", "Aa"),
+    Err(Error::InvalidAlphabet {
+      chars: "0".into(),
+      position: 1
+    })
+  );
+  assert_eq!(
+    Alphabet::new("01", "ABa"),
+    Err(Error::InvalidAlphabet {
+      chars: "ABa".into(),
+      position: 3
+    })
+  );
+  assert_eq!(
+    Alphabet::new("0é", "Aa"),
+    Err(Error::InvalidAlphabet {
+      chars: "0é".into(),
+      position: 1
+    })
+  );
+
+  Ok(())
+}
@@ -0,0 +1,110 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{n_keys_between, n_keys_between_balanced, Alphabet, Error, FractionalIndex};
+
+fn check_keys(a: &Option<String>, b: &Option<String>, keys: &[String], n: usize) -> Result<(), Error> {
+  assert_eq!(keys.len(), n);
+  for key in keys {
+    FractionalIndex::parse(key)?;
+  }
+  for pair in keys.windows(2) {
+    assert!(pair[0] < pair[1], "{} >= {}", pair[0], pair[1]);
+  }
+  if let (Some(a), Some(first)) = (a, keys.first()) {
+    assert!(a < first, "{} >= {}", a, first);
+  }
+  if let (Some(b), Some(last)) = (b, keys.last()) {
+    assert!(last < b, "{} >= {}", last, b);
+  }
+  Ok(())
+}
+
+#[test]
+fn balanced_keys_test() -> Result<(), Error> {
+  let s = |key: &str| Some(key.to_owned());
+  let cases = [
+    (None, None, 1, vec!["aU"]),
+    (None, None, 3, vec!["aE", "aU", "ak"]),
+    (s("a5"), None, 3, vec!["aJ", "aX", "al"]),
+    (None, s("a0"), 3, vec!["ZE", "ZU", "Zk"]),
+    (None, s("a5"), 2, vec!["a1", "a3"]),
+    (s("a0"), s("a1"), 3, vec!["a0F", "a0V", "a0k"]),
+    (s("Zz"), s("a01"), 2, vec!["ZzL", "Zzg"]),
+    (s("a5"), s("c000"), 4, vec!["bBf", "bOF", "bap", "bnP"]),
+    (
+      s("zzzzzzzzzzzzzzzzzzzzzzzzzzz"),
+      None,
+      1,
+      vec!["zzzzzzzzzzzzzzzzzzzzzzzzzzzV"],
+    ),
+    (
+      None,
+      s("A000000000000000000000000001"),
+      1,
+      vec!["A000000000000000000000000000V"],
+    ),
+    (s("a0"), s("a1"), 0, vec![]),
+  ];
+  for (a, b, n, exp) in cases.iter() {
+    let keys = n_keys_between_balanced(a, b, *n)?;
+    check_keys(a, b, &keys, *n)?;
+    assert_eq!(&keys, exp, "{:?} {:?} {}", a, b, n);
+  }
+
+  assert_eq!(
+    n_keys_between_balanced(&s("a1"), &s("a0"), 2),
+    Err(Error::InvalidOrder {
+      a: "a1".into(),
+      b: "a0".into()
+    })
+  );
+  Ok(())
+}
+
+#[test]
+fn balanced_lengths_test() -> Result<(), Error> {
+  let bounds = [
+    (None, None),
+    (Some("a0".to_owned()), None),
+    (None, Some("a0".to_owned())),
+    (Some("a0".to_owned()), Some("a1".to_owned())),
+    (Some("Xb0M".to_owned()), Some("Xb0M0V".to_owned())),
+    (Some("a0zzzz".to_owned()), Some("a1".to_owned())),
+  ];
+  for (a, b) in bounds.iter() {
+    for n in [1, 2, 10, 61, 62, 100, 1000, 5000] {
+      let keys = n_keys_between_balanced(a, b, n)?;
+      check_keys(a, b, &keys, n)?;
+
+      // never more than a digit longer than the unbalanced keys
+      let longest = keys.iter().map(String::len).max().unwrap();
+      let unbalanced = n_keys_between(a, b, n)?;
+      assert!(longest <= unbalanced.iter().map(String::len).max().unwrap() + 1);
+    }
+  }
+  Ok(())
+}
+
+#[test]
+fn balanced_spacing_test() -> Result<(), Error> {
+  // 61 keys between a0 and a1 use every digit once
+  let keys = n_keys_between_balanced(&Some("a0".to_owned()), &Some("a1".to_owned()), 61)?;
+  let digits = Alphabet::BASE62.digits();
+  for (i, key) in keys.iter().enumerate() {
+    assert_eq!(key, &format!("a0{}", &digits[i + 1..i + 2]));
+  }
+  // one more takes another digit
+  let keys = n_keys_between_balanced(&Some("a0".to_owned()), &Some("a1".to_owned()), 62)?;
+  assert_eq!(keys.iter().map(String::len).max(), Some(4));
+
+  // 3 keys in 0-f, evenly spaced by 4
+  let keys = Alphabet::HEX.n_keys_between_balanced(&Some("80".to_owned()), &Some("81".to_owned()), 3)?;
+  assert_eq!(keys, vec!["804", "808", "80c"]);
+
+  let keys = Alphabet::BASE36.n_keys_between_balanced(&None, &None, 1000)?;
+  for key in keys.iter() {
+    Alphabet::BASE36.float64_approx(key)?;
+  }
+  assert!(keys.iter().all(|key| key.len() <= 4));
+  Ok(())
+}
@@ -0,0 +1,110 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::binary::{from_key, key_between, n_keys_between, to_key, validate_key};
+use lexicon_fractional_index::{n_keys_between as n_str_keys_between, Error};
+
+#[test]
+fn binary_keys_test() -> Result<(), Error> {
+  fn test_check(a: Option<&[u8]>, b: Option<&[u8]>, exp: &[u8]) -> Result<(), Error> {
+    let act = key_between(&a.map(ToOwned::to_owned), &b.map(ToOwned::to_owned))?;
+    assert_eq!(exp, act.as_slice());
+    Ok(())
+  }
+
+  test_check(None, None, &[0x80, 0x00])?;
+  test_check(None, Some(&[0x80, 0x00]), &[0x7f, 0xff])?;
+  test_check(Some(&[0x80, 0x00]), None, &[0x80, 0x01])?;
+  test_check(Some(&[0x80, 0xff]), None, &[0x81, 0x00, 0x00])?;
+  test_check(Some(&[0x80, 0x00]), Some(&[0x80, 0x01]), &[0x80, 0x00, 0x80])?;
+  test_check(Some(&[0x80, 0x00, 0x80]), Some(&[0x80, 0x01]), &[0x80, 0x00, 0xc0])?;
+  test_check(Some(&[0x7f, 0xff]), Some(&[0x80, 0x00]), &[0x7f, 0xff, 0x80])?;
+  test_check(None, Some(&[0x7e, 0x00, 0x00]), &[0x7d, 0xff, 0xff, 0xff])?;
+
+  assert_eq!(
+    key_between(&Some(vec![0x80, 0x01]), &Some(vec![0x80, 0x00])),
+    Err(Error::InvalidOrder {
+      a: "\\x80\\x01".into(),
+      b: "\\x80\\x00".into()
+    })
+  );
+  assert_eq!(
+    validate_key(&[0x80, 0x01, 0x00]),
+    Err(Error::TrailingZero {
+      key: "\\x80\\x01\\x00".into()
+    })
+  );
+  assert_eq!(
+    validate_key(&[0x81, 0x01]),
+    Err(Error::TruncatedInteger {
+      key: "\\x81\\x01".into(),
+      expected_len: 3
+    })
+  );
+  assert_eq!(validate_key(&[]), Err(Error::EmptyKey));
+  assert_eq!(key_between(&Some(vec![]), &None), Err(Error::EmptyKey));
+
+  Ok(())
+}
+
+#[test]
+fn binary_n_keys_test() -> Result<(), Error> {
+  let keys = n_keys_between(&None, &None, 1000)?;
+  assert_eq!(keys.len(), 1000);
+  let start = n_keys_between(&None, &Some(keys[0].to_owned()), 1000)?;
+  let middle = n_keys_between(&Some(keys[0].to_owned()), &Some(keys[1].to_owned()), 1000)?;
+  for set in [&keys, &start, &middle].iter() {
+    for pair in set.windows(2) {
+      assert!(pair[0] < pair[1]);
+      validate_key(&pair[0])?;
+    }
+  }
+  assert!(start.last() < keys.first());
+  assert!(keys[0] < middle[0] && middle[999] < keys[1]);
+  // 1000 appended keys fit in 3 bytes, base62 needs 4 characters
+  assert_eq!(keys[999].len(), 3);
+
+  Ok(())
+}
+
+#[test]
+fn binary_conversion_test() -> Result<(), Error> {
+  assert_eq!(from_key("a0")?, vec![0x80, 0x00]);
+  assert_eq!(from_key("Zz")?, vec![0x7f, 61]);
+  assert_eq!(from_key("b1V")?, vec![0x81, 1, 31]);
+  assert_eq!(to_key(&[0x81, 1, 31])?, "b1V");
+
+  // order is preserved both ways
+  let keys = n_str_keys_between(&None, &Some("a0".to_owned()), 200)?;
+  let mut last: Option<Vec<u8>> = None;
+  for key in keys.iter() {
+    let binary = from_key(key)?;
+    assert_eq!(&to_key(&binary)?, key);
+    assert!(last < Some(binary.to_owned()));
+    last = Some(binary);
+  }
+  for key in ["A000000000000000000000000001", "zzzzzzzzzzzzzzzzzzzzzzzzzzzV"].iter() {
+    assert_eq!(&to_key(&from_key(key)?)?, key);
+  }
+
+  // not representable in base62
+  assert_eq!(
+    to_key(&[0x80, 62]),
+    Err(Error::InvalidDigit {
+      key: "\\x80>".into(),
+      position: 1
+    })
+  );
+  assert_eq!(
+    to_key(&[0x80, 0x00, 0xff]),
+    Err(Error::InvalidDigit {
+      key: "\\x80\\x00\\xff".into(),
+      position: 2
+    })
+  );
+  let mut long = vec![0xff];
+  long.extend(vec![0x01; 128]);
+  assert!(matches!(to_key(&long), Err(Error::InvalidHead { .. })));
+  assert_eq!(from_key("a00"), Err(Error::TrailingZero { key: "a00".into() }));
+
+  Ok(())
+}
@@ -0,0 +1,73 @@
+extern crate lexicon_fractional_index;
+
+use std::cmp::Ordering;
+
+use lexicon_fractional_index::{Alphabet, Error};
+use rusqlite::Connection;
+
+fn case_insensitive(a: &str, b: &str) -> Ordering {
+  a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+}
+
+#[test]
+fn corpus_test() -> Result<(), Error> {
+  for alphabet in [Alphabet::BASE62, Alphabet::BASE95, Alphabet::BASE36, Alphabet::HEX] {
+    let keys = alphabet.collation_corpus();
+    for key in keys.iter() {
+      alphabet.float64_approx(key)?;
+    }
+    for pair in keys.windows(2) {
+      assert!(pair[0] < pair[1], "{:?} >= {:?}", pair[0], pair[1]);
+    }
+    alphabet.verify_collation(str::cmp)?;
+  }
+
+  let keys = Alphabet::HEX.collation_corpus();
+  assert_eq!(keys.len(), 16 * 16 - 1 + 15 + 16 * 15);
+  assert_eq!(keys[..2], ["011111111", "022222222"]);
+  assert_eq!(keys[keys.len() - 1], "fffffffff");
+
+  Ok(())
+}
+
+#[test]
+fn case_insensitive_test() -> Result<(), Error> {
+  assert_eq!(
+    Alphabet::BASE62.verify_collation(case_insensitive),
+    Err(Error::CollationMismatch {
+      a: "AZZZZZZZZZZZZZZZZZZZZZZZZZZ".into(),
+      b: "Aaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+    })
+  );
+  Alphabet::BASE36.verify_collation(case_insensitive)?;
+  Alphabet::HEX.verify_collation(case_insensitive)?;
+
+  // a comparison that isn't a total order can't pass
+  assert!(Alphabet::HEX.verify_collation(|_, _| Ordering::Equal).is_err());
+
+  Ok(())
+}
+
+#[test]
+fn sqlite_collation_test() -> Result<(), Box<dyn std::error::Error>> {
+  let conn = Connection::open_in_memory()?;
+  conn.execute("CREATE TABLE items (key TEXT NOT NULL)", ())?;
+  let order_by = |alphabet: &Alphabet, collation: &str| -> rusqlite::Result<bool> {
+    conn.execute("DELETE FROM items", ())?;
+    let keys = alphabet.collation_corpus();
+    for key in keys.iter().rev() {
+      conn.execute("INSERT INTO items (key) VALUES (?1)", [key])?;
+    }
+    let mut select = conn.prepare(&format!("SELECT key FROM items ORDER BY key COLLATE {}", collation))?;
+    let sorted = select
+      .query_map((), |row| row.get(0))?
+      .collect::<Result<Vec<String>, _>>()?;
+    Ok(sorted == keys)
+  };
+
+  assert!(order_by(&Alphabet::BASE62, "BINARY")?);
+  assert!(!order_by(&Alphabet::BASE62, "NOCASE")?);
+  assert!(order_by(&Alphabet::BASE36, "NOCASE")?);
+
+  Ok(())
+}
@@ -0,0 +1,178 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::crdt::{Op, Sequence};
+use lexicon_fractional_index::Error;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// replicas connected by a network that delays, reorders and duplicates ops
+struct Network {
+  replicas: Vec<Sequence<u32>>,
+  /// ops in flight, with the replica they are sent to
+  in_flight: Vec<(usize, Op<u32>)>,
+  rng: StdRng,
+}
+
+impl Network {
+  fn new(replicas: u64, seed: u64) -> Self {
+    Network {
+      replicas: (0..replicas).map(Sequence::new).collect(),
+      in_flight: vec![],
+      rng: StdRng::seed_from_u64(seed),
+    }
+  }
+
+  fn broadcast(&mut self, from: usize, op: Op<u32>) {
+    for to in 0..self.replicas.len() {
+      if to != from {
+        self.in_flight.push((to, op.to_owned()));
+        if self.rng.gen_ratio(1, 10) {
+          self.in_flight.push((to, op.to_owned()));
+        }
+      }
+    }
+  }
+
+  /// delivers some of the ops in flight, in random order
+  fn deliver_some(&mut self) {
+    self.in_flight.shuffle(&mut self.rng);
+    let count = self.rng.gen_range(0..=self.in_flight.len());
+    for (to, op) in self.in_flight.split_off(self.in_flight.len() - count) {
+      self.replicas[to].apply(op);
+    }
+  }
+
+  fn deliver_all(&mut self) {
+    self.in_flight.shuffle(&mut self.rng);
+    for (to, op) in std::mem::take(&mut self.in_flight) {
+      self.replicas[to].apply(op);
+    }
+  }
+
+  /// a random local edit on replica r
+  fn edit(&mut self, r: usize, value: u32) -> Result<(), Error> {
+    let len = self.replicas[r].len();
+    let op = match self.rng.gen_range(0..4) {
+      0 | 1 => {
+        let index = self.rng.gen_range(0..=len);
+        self.replicas[r].insert(index, value)?
+      }
+      2 if len > 0 => {
+        let index = self.rng.gen_range(0..len);
+        self.replicas[r].delete(index)
+      }
+      3 if len > 0 => {
+        let (from, to) = (self.rng.gen_range(0..len), self.rng.gen_range(0..len));
+        self.replicas[r].move_to(from, to)?
+      }
+      _ => return Ok(()),
+    };
+    self.broadcast(r, op);
+    Ok(())
+  }
+
+  fn contents(&self) -> Vec<Vec<u32>> {
+    self
+      .replicas
+      .iter()
+      .map(|replica| replica.iter().copied().collect())
+      .collect()
+  }
+}
+
+#[test]
+fn local_edits_test() -> Result<(), Error> {
+  let mut seq = Sequence::new(1);
+  seq.insert(0, 'b')?;
+  seq.insert(0, 'a')?;
+  seq.insert(2, 'd')?;
+  seq.insert(2, 'c')?;
+  assert_eq!(seq.iter().collect::<String>(), "abcd");
+
+  seq.move_to(0, 3)?;
+  assert_eq!(seq.iter().collect::<String>(), "bcda");
+  seq.move_to(2, 0)?;
+  assert_eq!(seq.iter().collect::<String>(), "dbca");
+
+  let id = seq.id_at(1);
+  let delete = seq.delete(1);
+  assert_eq!(seq.iter().collect::<String>(), "dca");
+  assert_eq!(seq.tombstones(), 1);
+  assert_ne!(seq.id_at(1), id);
+
+  // applying an op again changes nothing
+  seq.apply(delete);
+  assert_eq!(seq.iter().collect::<String>(), "dca");
+
+  // inserting where the tombstone is doesn't reuse its key
+  seq.insert(1, 'x')?;
+  assert_eq!(seq.iter().collect::<String>(), "dxca");
+  seq.purge_tombstones();
+  assert_eq!(seq.tombstones(), 0);
+  assert_eq!(seq.iter().collect::<String>(), "dxca");
+  assert_eq!(seq.len(), 4);
+  Ok(())
+}
+
+#[test]
+fn concurrent_edits_test() -> Result<(), Error> {
+  let mut a = Sequence::new(1);
+  let mut b = Sequence::new(2);
+  let base = a.insert(0, 'x')?;
+  b.apply(base);
+
+  // both insert after x, replica 1 sorts first
+  let op_a = a.insert(1, 'a')?;
+  let op_b = b.insert(1, 'b')?;
+  a.apply(op_b.to_owned());
+  b.apply(op_a.to_owned());
+  assert_eq!(a.iter().collect::<String>(), "xab");
+  assert_eq!(b.iter().collect::<String>(), "xab");
+
+  // concurrent moves of x, the later op wins
+  let move_a = a.move_to(0, 2)?;
+  let move_b = b.move_to(0, 1)?;
+  let move_b2 = b.move_to(1, 2)?;
+  a.apply(move_b);
+  a.apply(move_b2.to_owned());
+  b.apply(move_a);
+  assert_eq!(a.iter().collect::<String>(), b.iter().collect::<String>());
+  assert_eq!(a.iter().collect::<String>(), "abx");
+
+  // delete before the insert it deletes arrives
+  let mut c = Sequence::new(3);
+  let insert = a.insert(0, 'y')?;
+  let delete = a.delete(0);
+  c.apply(delete);
+  c.apply(insert);
+  assert_eq!(c.len(), 0);
+  assert_eq!(c.tombstones(), 1);
+  Ok(())
+}
+
+#[test]
+fn simulation_test() -> Result<(), Error> {
+  for seed in 0..20 {
+    let mut network = Network::new(4, seed);
+    for value in 0..300 {
+      let r = network.rng.gen_range(0..4);
+      network.edit(r, value)?;
+      if network.rng.gen_ratio(1, 5) {
+        network.deliver_some();
+      }
+    }
+    network.deliver_all();
+
+    let contents = network.contents();
+    for content in contents.iter() {
+      assert_eq!(content, &contents[0], "seed {}", seed);
+    }
+    // every inserted value at most once
+    let mut values = contents[0].to_owned();
+    values.sort_unstable();
+    values.dedup();
+    assert_eq!(values.len(), contents[0].len());
+  }
+  Ok(())
+}
@@ -0,0 +1,66 @@
+extern crate lexicon_fractional_index;
+
+use diesel::prelude::*;
+use diesel::result::Error as DieselError;
+use diesel::sql_query;
+use lexicon_fractional_index::{Error, FractionalIndex};
+
+diesel::table! {
+  items (id) {
+    id -> Integer,
+    key -> Text,
+  }
+}
+
+fn connection() -> SqliteConnection {
+  let mut conn = SqliteConnection::establish(":memory:").unwrap();
+  sql_query("CREATE TABLE items (id INTEGER PRIMARY KEY, key TEXT NOT NULL)")
+    .execute(&mut conn)
+    .unwrap();
+  conn
+}
+
+#[test]
+fn round_trip_test() -> Result<(), Box<dyn std::error::Error>> {
+  let mut conn = connection();
+  let a = FractionalIndex::default();
+  let b = a.after()?;
+  let c = a.between(&b)?;
+  for key in [&b, &c, &a] {
+    diesel::insert_into(items::table)
+      .values(items::key.eq(key))
+      .execute(&mut conn)?;
+  }
+
+  let keys: Vec<FractionalIndex> = items::table.select(items::key).order(items::key).load(&mut conn)?;
+  assert_eq!(keys, [a.to_owned(), c, b]);
+
+  let first: FractionalIndex = items::table
+    .select(items::key)
+    .filter(items::key.lt(FractionalIndex::parse("a1")?))
+    .order(items::key)
+    .first(&mut conn)?;
+  assert_eq!(first, a);
+
+  Ok(())
+}
+
+#[test]
+fn invalid_key_test() {
+  let mut conn = connection();
+  sql_query("INSERT INTO items (key) VALUES ('a00')")
+    .execute(&mut conn)
+    .unwrap();
+  let err = items::table
+    .select(items::key)
+    .first::<FractionalIndex>(&mut conn)
+    .unwrap_err();
+  match err {
+    // the key error is the cause of the field error
+    DieselError::DeserializationError(e) => assert_eq!(
+      e.source().and_then(|e| e.downcast_ref::<Error>()),
+      Some(&Error::TrailingZero { key: "a00".into() })
+    ),
+    e => panic!("unexpected error: {}", e),
+  }
+}
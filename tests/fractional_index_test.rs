@@ -0,0 +1,86 @@
+extern crate lexicon_fractional_index;
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use lexicon_fractional_index::{key_between, Error, FractionalIndex};
+
+#[test]
+fn parse_test() -> Result<(), Error> {
+  assert_eq!(FractionalIndex::parse("a0")?.as_str(), "a0");
+  assert_eq!(FractionalIndex::try_from("Zz")?.to_string(), "Zz");
+  assert_eq!(FractionalIndex::try_from("b125".to_owned())?.as_ref(), "b125");
+  assert_eq!("a0V".parse::<FractionalIndex>()?.into_string(), "a0V");
+  assert_eq!(FractionalIndex::default().as_str(), "a0");
+
+  assert_eq!(
+    FractionalIndex::parse("a00"),
+    Err(Error::TrailingZero { key: "a00".into() })
+  );
+  assert_eq!(FractionalIndex::parse("0"), Err(Error::InvalidHead { key: "0".into() }));
+  assert_eq!(
+    FractionalIndex::parse("A00000000000000000000000000"),
+    Err(Error::SmallestKey {
+      key: "A00000000000000000000000000".into()
+    })
+  );
+
+  Ok(())
+}
+
+#[test]
+fn generate_test() -> Result<(), Error> {
+  let a = FractionalIndex::parse("a0")?;
+  let b = FractionalIndex::parse("a1")?;
+
+  assert_eq!(a.between(&b)?.as_str(), "a0V");
+  assert_eq!(a.before()?.as_str(), "Zz");
+  assert_eq!(b.after()?.as_str(), "a2");
+  assert_eq!(
+    b.between(&a),
+    Err(Error::InvalidOrder {
+      a: "a1".into(),
+      b: "a0".into()
+    })
+  );
+  assert_eq!(
+    a.between(&a),
+    Err(Error::InvalidOrder {
+      a: "a0".into(),
+      b: "a0".into()
+    })
+  );
+
+  // key_between returns the reserved smallest integer here, before doesn't
+  let second = FractionalIndex::parse("A00000000000000000000000001")?;
+  let first = second.before()?;
+  assert_eq!(first.as_str(), "A00000000000000000000000000V");
+  assert_eq!(FractionalIndex::parse(first.as_str())?, first);
+  assert!(first < second);
+
+  // same results as the unchecked string api
+  let left = FractionalIndex::parse("Xb0M")?;
+  let mut right = FractionalIndex::parse("Xb0M0V")?;
+  for _ in 0..100 {
+    let next = left.between(&right)?;
+    let expected = key_between(&Some(left.to_string()), &Some(right.to_string()))?;
+    assert_eq!(next.as_str(), expected);
+    assert!(left < next && next < right);
+    right = next;
+  }
+
+  Ok(())
+}
+
+#[test]
+fn hash_test() -> Result<(), Error> {
+  let mut set = HashSet::new();
+  let mut key = FractionalIndex::default();
+  for _ in 0..100 {
+    key = key.after()?;
+    assert!(set.insert(key.clone()));
+  }
+  assert!(set.contains(&FractionalIndex::parse("a1")?));
+
+  Ok(())
+}
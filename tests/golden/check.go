@@ -0,0 +1,82 @@
+// Replays key_between.jsonl against the Go package this crate was ported from:
+//
+//	cd tests/golden && go mod init check && go get github.com/rocicorp/fracdex
+//	go run check.go
+//
+// Go takes "" for an open bound, cases with an empty key are skipped.
+// Only whether a case fails is compared for errors, messages differ between versions.
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"os"
+	"reflect"
+
+	"github.com/rocicorp/fracdex"
+)
+
+type golden struct {
+	A     *string  `json:"a"`
+	B     *string  `json:"b"`
+	N     *uint    `json:"n"`
+	Keys  []string `json:"keys"`
+	Error *string  `json:"error"`
+}
+
+func bound(key *string) (string, bool) {
+	if key == nil {
+		return "", true
+	}
+	return *key, *key != ""
+}
+
+func main() {
+	file, err := os.Open("key_between.jsonl")
+	if err != nil {
+		panic(err)
+	}
+	defer file.Close()
+
+	scanner := bufio.NewScanner(file)
+	cases, skipped, mismatches := 0, 0, 0
+	for line := 1; scanner.Scan(); line++ {
+		var c golden
+		if err := json.Unmarshal(scanner.Bytes(), &c); err != nil {
+			panic(err)
+		}
+		cases++
+		a, okA := bound(c.A)
+		b, okB := bound(c.B)
+		if !okA || !okB {
+			skipped++
+			continue
+		}
+
+		var keys []string
+		if c.N == nil {
+			var key string
+			key, err = fracdex.KeyBetween(a, b)
+			keys = []string{key}
+		} else {
+			keys, err = fracdex.NKeysBetween(a, b, *c.N)
+		}
+		ok := (c.Error != nil) == (err != nil)
+		if ok && err == nil {
+			ok = reflect.DeepEqual(keys, c.Keys) || (len(keys) == 0 && len(c.Keys) == 0)
+		}
+		if !ok {
+			mismatches++
+			fmt.Printf("line %d: %s\n  got %v, %v\n", line, scanner.Text(), keys, err)
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		panic(err)
+	}
+
+	fmt.Printf("%d cases, %d skipped, %d mismatches\n", cases, skipped, mismatches)
+	if mismatches > 0 {
+		os.Exit(1)
+	}
+}
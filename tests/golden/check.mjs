@@ -0,0 +1,30 @@
+// Replays key_between.jsonl against the JavaScript package this crate was ported from:
+//
+//   npm install --no-save fractional-indexing
+//   node tests/golden/check.mjs
+//
+// Only whether a case fails is compared for errors, messages differ between versions.
+import { readFileSync } from "node:fs";
+import { generateKeyBetween, generateNKeysBetween } from "fractional-indexing";
+
+const path = new URL("./key_between.jsonl", import.meta.url);
+const lines = readFileSync(path, "utf8").split("\n").filter((line) => line !== "");
+
+let mismatches = 0;
+lines.forEach((line, i) => {
+  const { a, b, n, keys, error } = JSON.parse(line);
+  let actual;
+  try {
+    actual = n === null ? [generateKeyBetween(a, b)] : generateNKeysBetween(a, b, n);
+  } catch (e) {
+    actual = undefined;
+  }
+  const ok = error !== undefined ? actual === undefined : JSON.stringify(actual) === JSON.stringify(keys);
+  if (!ok) {
+    mismatches++;
+    console.log(`line ${i + 1}: ${line}\n  got ${actual === undefined ? "an error" : JSON.stringify(actual)}`);
+  }
+});
+
+console.log(`${lines.length} cases, ${mismatches} mismatches`);
+process.exit(mismatches === 0 ? 0 : 1);
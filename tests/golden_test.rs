@@ -0,0 +1,61 @@
+//! replays the golden vectors of tests/golden, generated by `examples/golden.rs`.
+
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{key_between, n_keys_between};
+use serde_json::Value;
+
+const GOLDEN: &str = include_str!("golden/key_between.jsonl");
+
+fn key(case: &Value, field: &str) -> Option<String> {
+  case[field].as_str().map(ToOwned::to_owned)
+}
+
+#[test]
+fn golden_test() {
+  let mut count = 0;
+  for (line, case) in GOLDEN.lines().enumerate() {
+    let case: Value = serde_json::from_str(case).unwrap();
+    let (a, b) = (key(&case, "a"), key(&case, "b"));
+    let result = match case["n"].as_u64() {
+      None => key_between(&a, &b).map(|key| vec![key]),
+      Some(n) => n_keys_between(&a, &b, n as usize),
+    };
+    let result = result.map_err(|e| e.to_string());
+    let expected = match case["error"].as_str() {
+      Some(error) => Err(error.to_owned()),
+      None => Ok(serde_json::from_value::<Vec<String>>(case["keys"].to_owned()).unwrap()),
+    };
+    assert_eq!(result, expected, "line {}: {}", line + 1, case);
+
+    // one key is the same either way
+    if case["n"] == 1 {
+      assert_eq!(
+        key_between(&a, &b).map_err(|e| e.to_string()),
+        expected.map(|keys| keys[0].to_owned())
+      );
+    }
+    count += 1;
+  }
+  assert!(count > 3000, "only {} golden vectors", count);
+}
+
+#[test]
+fn golden_keys_sorted_test() {
+  for case in GOLDEN.lines() {
+    let case: Value = serde_json::from_str(case).unwrap();
+    let Some(keys) = case["keys"].as_array() else {
+      continue;
+    };
+    let keys: Vec<&str> = keys.iter().map(|key| key.as_str().unwrap()).collect();
+    let first = key(&case, "a").into_iter();
+    let last = key(&case, "b").into_iter();
+    let all: Vec<String> = first
+      .chain(keys.iter().map(|key| key.to_string()))
+      .chain(last)
+      .collect();
+    for pair in all.windows(2) {
+      assert!(pair[0] < pair[1], "{} >= {} in {}", pair[0], pair[1], case);
+    }
+  }
+}
@@ -0,0 +1,76 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{analyze, key_between, n_keys_between, n_keys_between_balanced, Alphabet, Error};
+
+#[test]
+fn analyze_test() -> Result<(), Error> {
+  let stats = analyze(&["Zz", "a0", "a0V", "a0VVVVV", "b125"], 5)?;
+  assert_eq!(stats.count, 5);
+  assert_eq!(stats.histogram, vec![0, 0, 2, 1, 1, 0, 0, 1]);
+  assert_eq!(stats.mean_len, 18.0 / 5.0);
+  assert_eq!(stats.max_len, 7);
+  assert_eq!(stats.max_fraction_len, 5);
+  assert_eq!(stats.longest, vec![3, 4, 2, 0, 1]);
+  assert_eq!(stats.heads_below, 25);
+  assert_eq!(stats.heads_above, 24);
+  assert_eq!(stats.windows, vec![3..4]);
+  assert!(stats.needs_rebalance());
+
+  let stats = analyze::<&str>(&[], 5)?;
+  assert_eq!(stats.count, 0);
+  assert_eq!(stats.max_len, 0);
+  assert_eq!(stats.mean_len, 0.0);
+  assert!(stats.windows.is_empty());
+  assert!(!stats.needs_rebalance());
+
+  // appending until the last head
+  let stats = analyze(&["a0", "zzzzzzzzzzzzzzzzzzzzzzzzzzz"], 30)?;
+  assert_eq!(stats.heads_above, 0);
+  assert!(stats.windows.is_empty());
+  assert!(stats.needs_rebalance());
+
+  let stats = Alphabet::HEX.analyze(&["000000001", "80"], 8)?;
+  assert_eq!((stats.heads_below, stats.heads_above), (0, 7));
+
+  assert_eq!(
+    analyze(&["a1", "a0"], 5),
+    Err(Error::InvalidOrder {
+      a: "a1".into(),
+      b: "a0".into()
+    })
+  );
+  Ok(())
+}
+
+#[test]
+fn analyze_windows_test() -> Result<(), Error> {
+  // two clusters of middle insertions, far from each other
+  let mut keys = n_keys_between(&None, &None, 20)?;
+  for at in [3, 15] {
+    let left = Some(keys[at].to_owned());
+    let mut right = keys[at + 1].to_owned();
+    for _ in 0..100 {
+      right = key_between(&left, &Some(right))?;
+      keys.push(right.to_owned());
+    }
+  }
+  keys.sort();
+
+  let stats = analyze(&keys, 8)?;
+  assert_eq!(stats.windows.len(), 2);
+  assert!(stats.max_fraction_len > 15);
+  for window in stats.windows.iter().rev() {
+    // every window is rewritten between its neighbours
+    let a = keys.get(window.start.wrapping_sub(1)).cloned();
+    let b = keys.get(window.end).cloned();
+    let new_keys = n_keys_between_balanced(&a, &b, window.len())?;
+    keys.splice(window.to_owned(), new_keys);
+  }
+  assert_eq!(analyze(&keys, 8)?.windows, vec![]);
+  assert!(analyze(&keys, 8)?.max_len <= 8);
+
+  // windows touching each other are merged
+  let stats = analyze(&["a0", "a0V", "a0V1", "a0V11111111", "a0V2", "a0V21111111", "a1"], 4)?;
+  assert_eq!(stats.windows.len(), 1);
+  Ok(())
+}
@@ -0,0 +1,68 @@
+//! only uses what builds without the `alloc` feature:
+//! `cargo test --no-default-features --test heapless_test`
+
+extern crate lexicon_fractional_index;
+
+use std::convert::TryFrom;
+
+use lexicon_fractional_index::{key_between_into, key_between_stack, Alphabet, Error, KeyBuf, StackKey};
+
+#[test]
+fn key_between_stack_test() -> Result<(), Error> {
+  let a = key_between_stack::<8>(None, None)?;
+  assert_eq!(a.as_str(), "a0");
+  let b = key_between_stack::<8>(Some(a.as_str()), None)?;
+  assert_eq!(b.as_str(), "a1");
+  let c = key_between_stack::<8>(Some(a.as_str()), Some(b.as_str()))?;
+  assert_eq!(c.as_str(), "a0V");
+  assert!(a < c && c < b);
+
+  let hex = Alphabet::HEX.key_between_stack::<16>(None, None)?;
+  assert_eq!(hex.as_str(), "80");
+
+  // keep splitting the first gap until the key no longer fits
+  let mut last = c;
+  let err = loop {
+    match key_between_stack::<8>(Some(a.as_str()), Some(last.as_str())) {
+      Ok(key) => last = key,
+      Err(e) => break e,
+    }
+  };
+  assert_eq!(err, Error::BufferFull { capacity: 8 });
+  assert_eq!(last.len(), 8);
+
+  Ok(())
+}
+
+#[test]
+fn stored_key_test() -> Result<(), Error> {
+  let stored = StackKey::<8>::try_from("a0V")?;
+  let mut out = StackKey::<8>::new();
+  key_between_into(Some(stored.as_str()), None, &mut out)?;
+  assert_eq!(out.as_str(), "a1");
+
+  assert_eq!(StackKey::<2>::try_from("a0V"), Err(Error::BufferFull { capacity: 2 }));
+  assert!(matches!(
+    StackKey::<8>::try_from("a0é"),
+    Err(Error::InvalidDigit { position: 2, .. })
+  ));
+
+  Ok(())
+}
+
+#[test]
+fn error_test() {
+  let err = key_between_stack::<8>(Some("a00"), None).unwrap_err();
+  // the same ErrorKey type with and without the alloc feature
+  assert_eq!(err, Error::TrailingZero { key: "a00".into() });
+  assert_eq!(err.to_string(), "invalid order key: a00");
+
+  // keys of errors are cut when there is no allocator
+  let long = "a0".to_owned() + &"1".repeat(100);
+  let err = key_between_stack::<8>(Some(&long), Some("a0")).unwrap_err();
+  match err {
+    Error::InvalidOrder { a, .. } if cfg!(feature = "alloc") => assert_eq!(a.as_str(), long),
+    Error::InvalidOrder { a, .. } => assert_eq!(a.as_str(), &long[..32]),
+    e => panic!("unexpected error: {}", e),
+  }
+}
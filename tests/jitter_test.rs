@@ -0,0 +1,113 @@
+extern crate lexicon_fractional_index;
+
+use std::collections::HashSet;
+
+use lexicon_fractional_index::{
+  key_between, key_between_jittered, n_keys_between_jittered, Alphabet, Error, FractionalIndex,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn check_between(a: &Option<String>, b: &Option<String>, key: &str) -> Result<(), Error> {
+  FractionalIndex::parse(key)?;
+  if let Some(a) = a {
+    assert!(a.as_str() < key, "{} >= {}", a, key);
+  }
+  if let Some(b) = b {
+    assert!(key < b.as_str(), "{} >= {}", key, b);
+  }
+  Ok(())
+}
+
+#[test]
+fn jittered_key_test() -> Result<(), Error> {
+  let mut rng = StdRng::seed_from_u64(7);
+  let mut next = || rng.gen::<u64>();
+
+  let bounds = [
+    (None, None),
+    (Some("a0".to_owned()), None),
+    (None, Some("a0".to_owned())),
+    (Some("a0".to_owned()), Some("a1".to_owned())),
+    (Some("Zz".to_owned()), Some("a0".to_owned())),
+    (Some("a0".to_owned()), Some("a0G".to_owned())),
+    (None, Some("A000000000000000000000000001".to_owned())),
+  ];
+  for (a, b) in bounds.iter() {
+    let mut keys = HashSet::new();
+    for _ in 0..1000 {
+      let key = key_between_jittered(a, b, &mut next, 32)?;
+      check_between(a, b, &key)?;
+      keys.insert(key);
+    }
+    // 1000 draws from 2^32 sub-intervals
+    assert_eq!(keys.len(), 1000);
+  }
+
+  // without entropy it is key_between
+  let (a, b) = (Some("a0".to_owned()), Some("a1".to_owned()));
+  assert_eq!(key_between_jittered(&a, &b, &mut next, 0)?, key_between(&a, &b)?);
+  // bisecting 30 times adds 5 to 6 digits
+  let key = key_between_jittered(&a, &b, &mut next, 30)?;
+  assert!(key.len() <= 3 + 6, "{}", key);
+
+  assert_eq!(
+    key_between_jittered(&b, &a, &mut next, 8),
+    Err(Error::InvalidOrder {
+      a: "a1".into(),
+      b: "a0".into()
+    })
+  );
+
+  Ok(())
+}
+
+#[test]
+fn concurrent_insert_test() -> Result<(), Error> {
+  // two replicas inserting between the same neighbours
+  let mut rng_a = StdRng::seed_from_u64(1);
+  let mut rng_b = StdRng::seed_from_u64(2);
+  let (left, right) = (Some("a0".to_owned()), Some("a1".to_owned()));
+  let mut left_a = left.to_owned();
+  let mut left_b = left.to_owned();
+  for _ in 0..500 {
+    let key_a = key_between_jittered(&left_a, &right, &mut || rng_a.gen::<u64>(), 16)?;
+    let key_b = key_between_jittered(&left_b, &right, &mut || rng_b.gen::<u64>(), 16)?;
+    assert_ne!(key_a, key_b);
+    left_a = Some(key_a);
+    left_b = Some(key_b);
+  }
+
+  Ok(())
+}
+
+#[test]
+fn jittered_n_keys_test() -> Result<(), Error> {
+  let mut rng = StdRng::seed_from_u64(42);
+  let mut next = || rng.gen::<u64>();
+
+  let bounds = [
+    (None, None),
+    (Some("a4".to_owned()), None),
+    (None, Some("a0".to_owned())),
+    (Some("a0".to_owned()), Some("a2".to_owned())),
+  ];
+  for (a, b) in bounds.iter() {
+    let keys = n_keys_between_jittered(a, b, 100, &mut next, 24)?;
+    assert_eq!(keys.len(), 100);
+    for pair in keys.windows(2) {
+      assert!(pair[0] < pair[1]);
+    }
+    for key in keys.iter() {
+      check_between(a, b, key)?;
+    }
+  }
+  assert!(n_keys_between_jittered(&None, &None, 0, &mut next, 24)?.is_empty());
+
+  let hex = Alphabet::HEX.n_keys_between_jittered(&None, &None, 20, &mut next, 24)?;
+  for pair in hex.windows(2) {
+    assert!(pair[0] < pair[1]);
+  }
+
+  Ok(())
+}
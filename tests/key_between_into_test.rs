@@ -0,0 +1,110 @@
+extern crate lexicon_fractional_index;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use lexicon_fractional_index::{binary, key_between, key_between_into, Alphabet, Error, KeyBuf, StackKey};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+// single test in this binary, so that no other test allocates while counting
+#[test]
+fn key_between_into_test() -> Result<(), Error> {
+  let cases = [
+    (None, None),
+    (None, Some("a0")),
+    (None, Some("Zz")),
+    (Some("a0"), None),
+    (Some("a0"), Some("a1")),
+    (Some("a0V"), Some("a1")),
+    (Some("Zz"), Some("a0")),
+    (Some("Zz"), Some("a1")),
+    (None, Some("Y00")),
+    (Some("bzz"), None),
+    (Some("b125"), Some("b129")),
+    (None, Some("b999")),
+    (None, Some("A000000000000000000000000001")),
+    (Some("zzzzzzzzzzzzzzzzzzzzzzzzzzz"), None),
+  ];
+
+  let mut string = String::with_capacity(64);
+  let mut stack = StackKey::<32>::new();
+  for (a, b) in cases.iter() {
+    let (a_owned, b_owned) = (a.map(ToOwned::to_owned), b.map(ToOwned::to_owned));
+    // key_between allocates at least the String it returns
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let exp = key_between(&a_owned, &b_owned)?;
+    assert!(ALLOCATIONS.load(Ordering::SeqCst) > before, "{:?} {:?}", a, b);
+
+    string.clear();
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    key_between_into(*a, *b, &mut string)?;
+    stack.clear();
+    key_between_into(*a, *b, &mut stack)?;
+    assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), before, "{:?} {:?}", a, b);
+
+    assert_eq!(string, exp);
+    assert_eq!(stack.as_str(), exp);
+  }
+
+  // appends, and leaves the buffer unchanged on error
+  let mut out = "key:".to_owned();
+  key_between_into(Some("a0"), Some("a1"), &mut out)?;
+  assert_eq!(out, "key:a0V");
+  assert_eq!(
+    key_between_into(Some("a1"), Some("a0"), &mut out),
+    Err(Error::InvalidOrder {
+      a: "a1".into(),
+      b: "a0".into()
+    })
+  );
+  assert_eq!(out, "key:a0V");
+
+  let mut small = StackKey::<4>::new();
+  assert_eq!(
+    key_between_into(Some("a0"), Some("a0001"), &mut small),
+    Err(Error::BufferFull { capacity: 4 })
+  );
+  assert!(small.is_empty());
+  key_between_into(Some("a0"), Some("a1"), &mut small)?;
+  assert_eq!(small.to_string(), "a0V");
+  assert!(StackKey::<4>::default() < small);
+
+  let mut hex = Vec::new();
+  Alphabet::HEX.key_between_into(Some("80"), Some("81"), &mut hex)?;
+  assert_eq!(hex, b"808");
+
+  let mut bytes = Vec::with_capacity(16);
+  let before = ALLOCATIONS.load(Ordering::SeqCst);
+  binary::key_between_into(Some(&[0x80, 0x00]), Some(&[0x80, 0x01]), &mut bytes)?;
+  assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), before);
+  assert_eq!(bytes, vec![0x80, 0x00, 0x80]);
+
+  // long keys through a reused buffer
+  let left = "Xb0M".to_owned();
+  let mut right = "Xb0M0V".to_owned();
+  for _ in 0..200 {
+    string.clear();
+    key_between_into(Some(&left), Some(&right), &mut string)?;
+    assert_eq!(string, key_between(&Some(left.to_owned()), &Some(right.to_owned()))?);
+    right.clone_from(&string);
+  }
+
+  Ok(())
+}
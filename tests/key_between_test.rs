@@ -1,16 +1,16 @@
 extern crate lexicon_fractional_index;
 
-use lexicon_fractional_index::{float64_approx, key_between, n_keys_between};
+use lexicon_fractional_index::{float64_approx, key_between, n_keys_between, Error};
 
 #[test]
-fn keys_test() -> Result<(), String> {
-  fn test_check(a: Option<&str>, b: Option<&str>, exp: &str) -> Result<(), String> {
+fn keys_test() -> Result<(), Error> {
+  fn test_check(a: Option<&str>, b: Option<&str>, exp: &str) -> Result<(), Error> {
     match key_between(&a.map(ToOwned::to_owned), &b.map(ToOwned::to_owned)) {
       Ok(act) => {
         assert_eq!(exp, act)
       }
       Err(err) => {
-        assert_eq!(exp, err)
+        assert_eq!(exp, err.to_string())
       }
     }
     Ok(())
@@ -59,15 +59,15 @@ fn keys_test() -> Result<(), String> {
 }
 
 #[test]
-fn test_n_keys() -> Result<(), String> {
-  fn test_check(a: Option<&str>, b: Option<&str>, n: usize, exp: &str) -> Result<(), String> {
+fn test_n_keys() -> Result<(), Error> {
+  fn test_check(a: Option<&str>, b: Option<&str>, n: usize, exp: &str) -> Result<(), Error> {
     match n_keys_between(&a.map(ToOwned::to_owned), &b.map(ToOwned::to_owned), n) {
       Ok(act_slice) => {
         let act = act_slice.join(" ");
         assert_eq!(exp, act);
       }
       Err(err) => {
-        assert_eq!(exp, err)
+        assert_eq!(exp, err.to_string())
       }
     }
 
@@ -87,11 +87,11 @@ fn test_n_keys() -> Result<(), String> {
 }
 
 #[test]
-fn test_to_float64_approx() -> Result<(), String> {
-  fn test_check(key: &str, exp: f64, exp_err: &str) -> Result<(), String> {
+fn test_to_float64_approx() -> Result<(), Error> {
+  fn test_check(key: &str, exp: f64, exp_err: &str) -> Result<(), Error> {
     match float64_approx(key) {
       Ok(act) => assert!((exp - act).abs() < f64::EPSILON),
-      Err(err) => assert_eq!(exp_err, err),
+      Err(err) => assert_eq!(exp_err, err.to_string()),
     }
 
     Ok(())
@@ -121,3 +121,61 @@ fn test_to_float64_approx() -> Result<(), String> {
 
   Ok(())
 }
+
+#[test]
+fn errors_test() {
+  fn between(a: Option<&str>, b: Option<&str>) -> Result<String, Error> {
+    key_between(&a.map(ToOwned::to_owned), &b.map(ToOwned::to_owned))
+  }
+
+  assert_eq!(between(Some("0"), None), Err(Error::InvalidHead { key: "0".into() }));
+  assert_eq!(
+    between(Some("b1"), None),
+    Err(Error::TruncatedInteger {
+      key: "b1".into(),
+      expected_len: 3
+    })
+  );
+  assert_eq!(
+    between(None, Some("a10")),
+    Err(Error::TrailingZero { key: "a10".into() })
+  );
+  assert_eq!(
+    between(None, Some("A00000000000000000000000000")),
+    Err(Error::SmallestKey {
+      key: "A00000000000000000000000000".into()
+    })
+  );
+  assert_eq!(
+    between(Some("a1"), Some("a1")),
+    Err(Error::InvalidOrder {
+      a: "a1".into(),
+      b: "a1".into()
+    })
+  );
+  assert_eq!(
+    between(
+      Some("zzzzzzzzzzzzzzzzzzzzzzzzzzz"),
+      Some("zzzzzzzzzzzzzzzzzzzzzzzzzzzV")
+    ),
+    Ok("zzzzzzzzzzzzzzzzzzzzzzzzzzzG".to_owned())
+  );
+  assert_eq!(
+    float64_approx("a0!"),
+    Err(Error::InvalidDigit {
+      key: "a0!".into(),
+      position: 2
+    })
+  );
+  assert_eq!(
+    float64_approx("b!0"),
+    Err(Error::InvalidDigit {
+      key: "b!0".into(),
+      position: 1
+    })
+  );
+  assert_eq!(float64_approx(""), Err(Error::EmptyKey));
+
+  let err: Box<dyn std::error::Error> = Box::new(Error::RangeOverflow);
+  assert_eq!(err.to_string(), "range overflow");
+}
@@ -0,0 +1,101 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{Error, FractionalList};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn values<T: Clone>(list: &FractionalList<T>) -> Vec<T> {
+  list.iter().map(|(_, value)| value.to_owned()).collect()
+}
+
+#[test]
+fn list_test() -> Result<(), Error> {
+  let mut list = FractionalList::new();
+  assert!(list.is_empty());
+
+  let b = list.push_back("b")?;
+  let c = list.push_back("c")?;
+  let a = list.push_front("a")?;
+  assert_eq!(values(&list), vec!["a", "b", "c"]);
+  assert_eq!(list.key(b).map(|key| key.as_str()), Some("a0"));
+
+  let b2 = list.insert_after(b, "b2")?;
+  let b1 = list.insert_before(b2, "b1")?;
+  let z = list.insert_after(c, "z")?;
+  let under = list.insert_before(a, "_")?;
+  assert_eq!(values(&list), vec!["_", "a", "b", "b1", "b2", "c", "z"]);
+  assert_eq!(list.len(), 7);
+
+  list.move_to(z, 0)?;
+  list.move_to(a, 6)?;
+  list.move_to(b1, 4)?;
+  assert_eq!(values(&list), vec!["z", "_", "b", "b2", "b1", "c", "a"]);
+  // keys of other elements don't change
+  assert_eq!(list.key(b).map(|key| key.as_str()), Some("a0"));
+
+  assert_eq!(list.remove(b2), Some("b2"));
+  assert_eq!(list.remove(b2), None);
+  assert!(!list.contains(b2));
+  assert_eq!(list.insert_after(b2, "x"), Err(Error::UnknownId { id: b2.get() }));
+  assert_eq!(list.move_to(b2, 0), Err(Error::UnknownId { id: b2.get() }));
+
+  *list.get_mut(c).unwrap() = "C";
+  assert_eq!(list.get(c), Some(&"C"));
+  assert_eq!(values(&list), vec!["z", "_", "b", "b1", "C", "a"]);
+
+  // ids are stable and unique
+  let ids: Vec<_> = list.iter().map(|(id, _)| id).collect();
+  assert_eq!(ids, vec![z, under, b, b1, c, a]);
+  Ok(())
+}
+
+#[test]
+#[should_panic]
+fn move_out_of_bounds_test() {
+  let mut list = FractionalList::new();
+  let a = list.push_back(1).unwrap();
+  list.move_to(a, 1).unwrap();
+}
+
+#[test]
+fn list_model_test() -> Result<(), Error> {
+  let mut rng = StdRng::seed_from_u64(12);
+  let mut list = FractionalList::new();
+  let mut model = vec![];
+
+  for value in 0..2000 {
+    match rng.gen_range(0..6) {
+      0 => {
+        model.insert(0, (list.push_front(value)?, value));
+      }
+      1 => {
+        model.push((list.push_back(value)?, value));
+      }
+      2 | 3 if !model.is_empty() => {
+        let i = rng.gen_range(0..model.len());
+        if rng.gen() {
+          let id = list.insert_before(model[i].0, value)?;
+          model.insert(i, (id, value));
+        } else {
+          let id = list.insert_after(model[i].0, value)?;
+          model.insert(i + 1, (id, value));
+        }
+      }
+      4 if !model.is_empty() => {
+        let (from, to) = (rng.gen_range(0..model.len()), rng.gen_range(0..model.len()));
+        let element = model.remove(from);
+        list.move_to(element.0, to)?;
+        model.insert(to, element);
+      }
+      5 if !model.is_empty() => {
+        let (id, value) = model.remove(rng.gen_range(0..model.len()));
+        assert_eq!(list.remove(id), Some(value));
+      }
+      _ => {}
+    }
+    assert_eq!(list.len(), model.len());
+  }
+  let elements: Vec<_> = list.iter().map(|(id, value)| (id, *value)).collect();
+  assert_eq!(elements, model);
+  Ok(())
+}
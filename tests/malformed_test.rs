@@ -0,0 +1,223 @@
+extern crate lexicon_fractional_index;
+
+use std::convert::TryFrom;
+
+use lexicon_fractional_index::{
+  analyze, binary, float64_approx, key_between, key_between_into, key_between_jittered, key_between_stack,
+  key_between_with_replica, key_for_move, keys_for_block_move, n_keys_between, n_keys_between_balanced, rebalance,
+  rebalance_window, replica_id, Alphabet, Error, FractionalIndex, StackKey,
+};
+use proptest::prelude::*;
+
+const SMALLEST: &str = "A00000000000000000000000000";
+
+fn malformed() -> Vec<(&'static str, Error)> {
+  let digit = |key: &str, position| Error::InvalidDigit {
+    key: key.into(),
+    position,
+  };
+  vec![
+    ("", Error::EmptyKey),
+    (
+      "a",
+      Error::TruncatedInteger {
+        key: "a".into(),
+        expected_len: 2,
+      },
+    ),
+    (
+      "b1",
+      Error::TruncatedInteger {
+        key: "b1".into(),
+        expected_len: 3,
+      },
+    ),
+    ("é", Error::InvalidHead { key: "é".into() }),
+    ("-a0", Error::InvalidHead { key: "-a0".into() }),
+    ("0", Error::InvalidHead { key: "0".into() }),
+    ("aé", digit("aé", 1)),
+    ("a0é", digit("a0é", 2)),
+    ("a0-", digit("a0-", 2)),
+    ("a-", digit("a-", 1)),
+    ("a0 ", digit("a0 ", 2)),
+    ("a0\u{0}", digit("a0\u{0}", 2)),
+    ("a0V\u{301}", digit("a0V\u{301}", 3)),
+    ("b-0V", digit("b-0V", 1)),
+    ("a00", Error::TrailingZero { key: "a00".into() }),
+    (SMALLEST, Error::SmallestKey { key: SMALLEST.into() }),
+  ]
+}
+
+#[test]
+fn validate_test() {
+  for (key, err) in malformed() {
+    assert_eq!(FractionalIndex::parse(key), Err(err.clone()), "{:?}", key);
+    assert_eq!(key.parse::<FractionalIndex>(), Err(err.clone()), "{:?}", key);
+    assert_eq!(FractionalIndex::try_from(key.to_owned()), Err(err.clone()), "{:?}", key);
+    assert_eq!(float64_approx(key), Err(err.clone()), "{:?}", key);
+    assert_eq!(replica_id(key), Err(err.clone()), "{:?}", key);
+    assert_eq!(binary::from_key(key), Err(err), "{:?}", key);
+  }
+}
+
+#[test]
+fn key_between_test() {
+  for (key, err) in malformed() {
+    let some = Some(key.to_owned());
+    for (a, b) in [(&some, &None), (&None, &some), (&some, &Some("zz".to_owned()))] {
+      assert_eq!(key_between(a, b), Err(err.clone()), "{:?} {:?}", a, b);
+      assert_eq!(n_keys_between(a, b, 3), Err(err.clone()), "{:?} {:?}", a, b);
+      assert_eq!(n_keys_between_balanced(a, b, 3), Err(err.clone()), "{:?} {:?}", a, b);
+      assert_eq!(key_between_with_replica(a, b, 7), Err(err.clone()), "{:?} {:?}", a, b);
+      assert_eq!(
+        key_between_jittered(a, b, &mut || 1, 8),
+        Err(err.clone()),
+        "{:?} {:?}",
+        a,
+        b
+      );
+
+      let mut out = String::from("x");
+      assert_eq!(key_between_into(a.as_deref(), b.as_deref(), &mut out), Err(err.clone()));
+      assert_eq!(out, "x");
+      assert_eq!(key_between_stack::<64>(a.as_deref(), b.as_deref()), Err(err.clone()));
+    }
+  }
+}
+
+#[test]
+fn key_list_test() {
+  for (key, err) in malformed() {
+    let keys = ["a0", key, "a2"];
+    assert_eq!(rebalance(&keys), Err(err.clone()), "{:?}", key);
+    assert_eq!(rebalance_window(&keys, 1).map(|_| ()), Err(err.clone()), "{:?}", key);
+    assert_eq!(analyze(&keys, 1).map(|_| ()), Err(err.clone()), "{:?}", key);
+
+    // moves next to the malformed key, or of the malformed key itself
+    assert_eq!(key_for_move(&keys, 0, 1), Err(err.clone()), "{:?}", key);
+    assert_eq!(key_for_move(&keys, 1, 1), Err(err.clone()), "{:?}", key);
+    assert_eq!(keys_for_block_move(&keys, 1..2, 1), Err(err.clone()), "{:?}", key);
+    assert_eq!(keys_for_block_move(&keys, 0..1, 1), Err(err), "{:?}", key);
+  }
+}
+
+#[test]
+fn binary_test() {
+  let smallest = [vec![0x00], vec![0x00; 128]].concat();
+  for (key, err) in [
+    (vec![], Error::EmptyKey),
+    (
+      vec![0x80],
+      Error::TruncatedInteger {
+        key: "\\x80".into(),
+        expected_len: 2,
+      },
+    ),
+    (
+      vec![0x80, 0x00, 0x00],
+      Error::TrailingZero {
+        key: "\\x80\\x00\\x00".into(),
+      },
+    ),
+    (
+      smallest,
+      Error::SmallestKey {
+        key: "\0".repeat(129).into(),
+      },
+    ),
+  ] {
+    assert_eq!(binary::validate_key(&key), Err(err.clone()));
+    assert_eq!(binary::to_key(&key), Err(err.clone()));
+    assert_eq!(binary::key_between(&Some(key.clone()), &None), Err(err.clone()));
+    assert_eq!(binary::n_keys_between(&None, &Some(key.clone()), 2), Err(err.clone()));
+    let mut out = vec![];
+    assert_eq!(binary::key_between_into(Some(&key), None, &mut out), Err(err));
+  }
+
+  // valid, but without a base62 counterpart
+  assert_eq!(
+    binary::to_key(&[0x80, 0xff]),
+    Err(Error::InvalidDigit {
+      key: "\\x80\\xff".into(),
+      position: 1
+    })
+  );
+}
+
+#[test]
+fn alphabet_test() {
+  assert_eq!(
+    Alphabet::new("0é", "ab"),
+    Err(Error::InvalidAlphabet {
+      chars: "0é".into(),
+      position: 1
+    })
+  );
+  assert_eq!(
+    Alphabet::new("", "ab"),
+    Err(Error::InvalidAlphabet {
+      chars: "".into(),
+      position: 0
+    })
+  );
+  assert_eq!(
+    Alphabet::new("01", ""),
+    Err(Error::InvalidAlphabet {
+      chars: "".into(),
+      position: 0
+    })
+  );
+
+  // characters of other alphabets are not digits
+  assert_eq!(
+    Alphabet::HEX.key_between(&Some("8g".to_owned()), &None),
+    Err(Error::InvalidDigit {
+      key: "8g".into(),
+      position: 1
+    })
+  );
+  assert_eq!(
+    Alphabet::BASE36.key_between(&None, &Some("iA".to_owned())),
+    Err(Error::InvalidDigit {
+      key: "iA".into(),
+      position: 1
+    })
+  );
+}
+
+#[test]
+fn stack_key_test() {
+  assert_eq!(
+    StackKey::<8>::try_from("a0é"),
+    Err(Error::InvalidDigit {
+      key: "a0é".into(),
+      position: 2
+    })
+  );
+  assert_eq!(StackKey::<2>::try_from("a0V"), Err(Error::BufferFull { capacity: 2 }));
+}
+
+proptest! {
+  // whatever the input, every function returns, valid or not
+  #[test]
+  fn total_test(a in any::<String>(), b in "[A-Za-z]?[0-9A-Za-z]{0,4}[^0-9A-Za-z]?[0-9A-Za-z]{0,2}") {
+    let (some_a, some_b) = (Some(a.clone()), Some(b.clone()));
+    let _ = key_between(&some_a, &some_b);
+    let _ = key_between(&None, &some_b);
+    let _ = key_between(&some_b, &None);
+    let _ = n_keys_between(&some_b, &some_a, 3);
+    let _ = n_keys_between_balanced(&some_b, &None, 3);
+    let _ = key_between_with_replica(&None, &some_b, 3);
+    let _ = key_between_jittered(&some_b, &None, &mut || 5, 4);
+    let _ = key_between_stack::<16>(None, Some(&b));
+    let _ = replica_id(&b);
+    let _ = float64_approx(&b);
+    let _ = rebalance(&[&a, &b]);
+    let _ = analyze(&[&b], 2);
+    let _ = key_for_move(&["a0", &b, "a2"], 2, 1);
+    let _ = binary::from_key(&b);
+    let _ = binary::to_key(a.as_bytes());
+    let _ = binary::key_between(&Some(a.into_bytes()), &None);
+    let _ = StackKey::<4>::try_from(b.as_str());
+  }
+}
@@ -0,0 +1,47 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{migrate_positions, n_keys_between, Alphabet, Error};
+
+#[test]
+fn integer_positions_test() -> Result<(), Error> {
+  assert_eq!(migrate_positions(&[10, 20, 30])?, ["a0", "a1", "a2"]);
+  assert_eq!(migrate_positions(&[30, 10, 20])?, ["a2", "a0", "a1"]);
+  // ties keep the order of the input
+  assert_eq!(migrate_positions(&[5, 1, 5, 1, 5])?, ["a2", "a0", "a3", "a1", "a4"]);
+  assert_eq!(migrate_positions::<i64>(&[])?, Vec::<String>::new());
+  assert_eq!(migrate_positions(&[i64::MIN, i64::MAX, 0])?, ["a0", "a2", "a1"]);
+  Ok(())
+}
+
+#[test]
+fn float_positions_test() -> Result<(), Error> {
+  assert_eq!(
+    migrate_positions(&[0.5, -1.0, 0.25, f64::INFINITY, -0.0, 0.0])?,
+    ["a4", "a0", "a3", "a5", "a1", "a2"]
+  );
+  assert_eq!(Alphabet::HEX.migrate_positions(&[2.0, 1.0])?, ["81", "80"]);
+  assert_eq!(migrate_positions(&[1.0, f64::NAN]), Err(Error::Unrepresentable));
+  Ok(())
+}
+
+#[test]
+fn order_test() -> Result<(), Error> {
+  // many duplicates, and more keys than one head holds
+  let positions: Vec<u32> = (0..5000u32).map(|i| i.wrapping_mul(2654435761) % 1000).collect();
+  let keys = migrate_positions(&positions)?;
+
+  let mut sorted: Vec<(u32, usize)> = positions.iter().copied().zip(0..).collect();
+  sorted.sort();
+  let in_order: Vec<&String> = sorted.iter().map(|(_, i)| &keys[*i]).collect();
+  assert!(in_order.windows(2).all(|pair| pair[0] < pair[1]));
+
+  let mut expected = n_keys_between(&None, &None, positions.len())?;
+  expected.sort();
+  let mut actual = keys.clone();
+  actual.sort();
+  assert_eq!(actual, expected);
+
+  // the same positions give the same keys
+  assert_eq!(migrate_positions(&positions)?, keys);
+  Ok(())
+}
@@ -1,9 +1,9 @@
 extern crate lexicon_fractional_index;
 
-use lexicon_fractional_index::key_between;
+use lexicon_fractional_index::{key_between, Error};
 
 #[test]
-fn insert_end_test() -> Result<(), String> {
+fn insert_end_test() -> Result<(), Error> {
   let mut left: Option<String> = None;
   let right: Option<String> = None;
 
@@ -25,7 +25,7 @@ fn insert_end_test() -> Result<(), String> {
 }
 
 #[test]
-fn insert_beggining_test() -> Result<(), String> {
+fn insert_beggining_test() -> Result<(), Error> {
   let left: Option<String> = None;
   let mut right: Option<String> = None;
 
@@ -34,9 +34,9 @@ fn insert_beggining_test() -> Result<(), String> {
     assert_ne!(left.to_owned(), Some(next.to_owned()));
     assert_ne!(right.to_owned(), Some(next.to_owned()));
 
-    if right.is_some() {
+    if let Some(right) = right {
       // println!("< {:?} {:?} {:?}", left, next, right);
-      assert!(next < right.unwrap());
+      assert!(next < right);
     }
     right = Some(next);
   }
@@ -47,20 +47,20 @@ fn insert_beggining_test() -> Result<(), String> {
 }
 
 #[test]
-fn insert_middle_left_test() -> Result<(), String> {
+#[ignore = "grows keys to thousands of digits, run with --release -- --ignored"]
+fn insert_middle_left_test() -> Result<(), Error> {
   let left = Some("Zj".to_owned());
   let mut right = Some("Zk".to_owned());
 
-  // too slow to have 100k insertions, use 4k only
-  for _ in 0..4000 {
+  for _ in 0..100000 {
     let next = key_between(&left, &right)?;
     assert_ne!(left.as_ref().unwrap(), &next);
     assert_ne!(right.as_ref().unwrap(), &next);
 
-    if right.is_some() {
+    if let Some(right) = right {
       // println!("< {:?} {:?} {:?}", left, next, right);
       assert!(left.as_ref().unwrap() < &next);
-      assert!(next < right.unwrap());
+      assert!(next < right);
     }
     right = Some(next);
   }
@@ -71,11 +71,12 @@ fn insert_middle_left_test() -> Result<(), String> {
 }
 
 #[test]
-fn insert_middle_right_test() -> Result<(), String> {
+#[ignore = "grows keys to thousands of digits, run with --release -- --ignored"]
+fn insert_middle_right_test() -> Result<(), Error> {
   let mut left = Some("Zj".to_owned());
   let right = Some("Zk".to_owned());
 
-  for _ in 0..4000 {
+  for _ in 0..100000 {
     let next = key_between(&left, &right)?;
     assert_ne!(left.as_ref().unwrap(), &next);
     assert_ne!(right.as_ref().unwrap(), &next);
@@ -94,13 +95,14 @@ fn insert_middle_right_test() -> Result<(), String> {
 }
 
 #[test]
-fn insert_middle_left_right_test() -> Result<(), String> {
+#[ignore = "grows keys to thousands of digits, run with --release -- --ignored"]
+fn insert_middle_left_right_test() -> Result<(), Error> {
   let mut left = Some("Zj".to_owned());
   let mut right = Some("Zk".to_owned());
 
   let mut at_right = false;
 
-  for _ in 0..2000 {
+  for _ in 0..100000 {
     let next = key_between(&left, &right)?;
     assert_ne!(left.as_ref().unwrap(), &next);
     assert_ne!(right.as_ref().unwrap(), &next);
@@ -122,3 +124,17 @@ fn insert_middle_left_right_test() -> Result<(), String> {
 
   Ok(())
 }
+
+#[test]
+fn long_key_test() -> Result<(), Error> {
+  // every digit of a is consecutive to b, a recursive midpoint would go 1M frames deep
+  let left = Some(format!("a0{}", "z".repeat(1_000_000)));
+  let right = Some("a1".to_owned());
+
+  let next = key_between(&left, &right)?;
+  assert_eq!(next.len(), 1_000_003);
+  assert!(left.as_ref().unwrap() < &next);
+  assert!(&next < right.as_ref().unwrap());
+
+  Ok(())
+}
@@ -0,0 +1,191 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{Alphabet, Error, FractionalIndex};
+use proptest::prelude::*;
+
+const ALPHABETS: [Alphabet; 4] = [Alphabet::BASE62, Alphabet::BASE95, Alphabet::BASE36, Alphabet::HEX];
+
+/// fraction digits of a valid key, never ending with the zero digit
+fn fraction(alphabet: Alphabet) -> impl Strategy<Value = String> {
+  let digits = alphabet.digits().as_bytes().to_vec();
+  (
+    prop::collection::vec(0..digits.len(), 0..6),
+    1..digits.len(),
+    any::<bool>(),
+  )
+    .prop_map(move |(values, last, empty)| {
+      if empty {
+        return String::new();
+      }
+      values
+        .into_iter()
+        .chain([last])
+        .map(|value| digits[value] as char)
+        .collect()
+    })
+}
+
+/// valid keys, mostly with heads close to zero, where keys are short
+fn key(alphabet: Alphabet) -> impl Strategy<Value = String> {
+  let (heads, half) = (alphabet.heads().len(), alphabet.heads().len() / 2);
+  key_with_head(
+    alphabet,
+    prop_oneof![
+      3 => half.saturating_sub(2)..(half + 2).min(heads),
+      1 => 0..heads,
+    ],
+  )
+}
+
+/// valid keys with the head at an index drawn from head
+fn key_with_head(alphabet: Alphabet, head: impl Strategy<Value = usize>) -> impl Strategy<Value = String> {
+  let heads = alphabet.heads().as_bytes().to_vec();
+  let digits = alphabet.digits().as_bytes().to_vec();
+  let (first_head, zero) = (heads[0], digits[0]);
+  let half = heads.len() / 2;
+  (
+    head,
+    prop::collection::vec(0..digits.len(), heads.len()),
+    fraction(alphabet),
+  )
+    .prop_map(move |(head, values, fraction)| {
+      let int_len = if head < half { half - head } else { head - half + 1 };
+      let mut key = String::from(heads[head] as char);
+      key.extend(values[..int_len].iter().map(|value| digits[*value] as char));
+      key + &fraction
+    })
+    .prop_filter("smallest integer", move |key| {
+      key.len() != half + 1 || key.as_bytes()[0] != first_head || key[1..].bytes().any(|d| d != zero)
+    })
+}
+
+/// two different valid keys in order, often sharing a prefix
+fn ordered_pair(alphabet: Alphabet) -> impl Strategy<Value = (String, String)> {
+  order(prop_oneof![
+    (key(alphabet), key(alphabet)),
+    (key(alphabet), fraction(alphabet), fraction(alphabet)).prop_map(|(key, a, b)| (key.clone() + &a, key + &b)),
+  ])
+}
+
+/// two different valid keys in order, sharing a positive head
+fn positive_pair(alphabet: Alphabet) -> impl Strategy<Value = (String, String)> {
+  let heads = alphabet.heads().len();
+  (heads / 2..heads).prop_flat_map(move |head| {
+    order(prop_oneof![
+      (key_with_head(alphabet, Just(head)), key_with_head(alphabet, Just(head))),
+      (
+        key_with_head(alphabet, Just(head)),
+        fraction(alphabet),
+        fraction(alphabet)
+      )
+        .prop_map(|(key, a, b)| (key.clone() + &a, key + &b)),
+    ])
+  })
+}
+
+fn order(pairs: impl Strategy<Value = (String, String)>) -> impl Strategy<Value = (String, String)> {
+  pairs.prop_filter_map("equal keys", |(a, b)| match a.cmp(&b) {
+    std::cmp::Ordering::Less => Some((a, b)),
+    std::cmp::Ordering::Equal => None,
+    std::cmp::Ordering::Greater => Some((b, a)),
+  })
+}
+
+fn alphabet() -> impl Strategy<Value = Alphabet> {
+  prop::sample::select(&ALPHABETS[..])
+}
+
+/// bounds as passed to key_between, either of them open
+fn bounds() -> impl Strategy<Value = (Alphabet, Option<String>, Option<String>)> {
+  alphabet().prop_flat_map(|alphabet| {
+    (Just(alphabet), ordered_pair(alphabet), any::<bool>(), any::<bool>()).prop_map(
+      |(alphabet, (a, b), open_a, open_b)| (alphabet, Some(a).filter(|_| !open_a), Some(b).filter(|_| !open_b)),
+    )
+  })
+}
+
+/// checks that key is valid and strictly between a and b
+fn check_between(alphabet: &Alphabet, a: &Option<String>, b: &Option<String>, key: &str) -> Result<(), TestCaseError> {
+  match alphabet.validate(key) {
+    // before the integer after it, the reserved smallest integer is returned, as the JS
+    // and Go implementations do
+    Err(Error::SmallestKey { .. }) if a.is_none() => {}
+    result => prop_assert!(result.is_ok(), "{:?} is not a valid key", key),
+  }
+  if let Some(a) = a {
+    prop_assert!(a.as_str() < key, "{:?} is not after {:?}", key, a);
+  }
+  if let Some(b) = b {
+    prop_assert!(key < b.as_str(), "{:?} is not before {:?}", key, b);
+  }
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn key_between_test((alphabet, a, b) in bounds()) {
+    let key = alphabet.key_between(&a, &b).unwrap();
+    check_between(&alphabet, &a, &b, &key)?;
+  }
+
+  #[test]
+  fn n_keys_between_test((alphabet, a, b) in bounds(), n in 0..40usize) {
+    let keys = alphabet.n_keys_between(&a, &b, n).unwrap();
+    prop_assert_eq!(keys.len(), n);
+    for key in &keys {
+      check_between(&alphabet, &a, &b, key)?;
+    }
+    for pair in keys.windows(2) {
+      prop_assert!(pair[0] < pair[1], "{:?} is not before {:?}", pair[0], pair[1]);
+    }
+  }
+
+  #[test]
+  fn validate_test(key in any::<String>()) {
+    // must not panic, whatever the result
+    let _ = FractionalIndex::parse(&key);
+    for alphabet in &ALPHABETS {
+      let _ = alphabet.validate(&key);
+      let _ = alphabet.float64_approx(&key);
+    }
+  }
+
+  #[test]
+  fn validate_key_like_test(key in "[A-Za-z][0-9A-Za-z]{0,30}") {
+    let _ = FractionalIndex::parse(&key);
+    let _ = Alphabet::BASE62.float64_approx(&key);
+  }
+
+  // float64_approx is only monotone for keys sharing a positive head, see its doc
+  #[test]
+  fn float64_approx_monotone_test((alphabet, (a, b)) in alphabet().prop_flat_map(|alphabet| (Just(alphabet), positive_pair(alphabet)))) {
+    let (x, y) = (alphabet.float64_approx(&a).unwrap(), alphabet.float64_approx(&b).unwrap());
+    prop_assert!(x <= y, "{:?} is {} but {:?} is {}", a, x, b, y);
+  }
+}
+
+#[cfg(feature = "rational")]
+proptest! {
+  #[test]
+  fn rational_round_trip_test((alphabet, (a, b)) in alphabet().prop_flat_map(|alphabet| (Just(alphabet), ordered_pair(alphabet)))) {
+    let (x, y) = (alphabet.to_rational(&a).unwrap(), alphabet.to_rational(&b).unwrap());
+    prop_assert!(x < y, "{:?} is {} but {:?} is {}", a, x, b, y);
+    prop_assert_eq!(alphabet.from_rational(&x).unwrap(), a);
+  }
+
+  // unlike float64_approx, to_f64 keeps the order of all keys
+  #[test]
+  fn to_f64_monotone_test((alphabet, (a, b)) in alphabet().prop_flat_map(|alphabet| (Just(alphabet), ordered_pair(alphabet)))) {
+    let (x, y) = (alphabet.to_f64(&a).unwrap(), alphabet.to_f64(&b).unwrap());
+    prop_assert!(x <= y, "{:?} is {} but {:?} is {}", a, x, b, y);
+  }
+
+  // HEX keys end a little above 4e9
+  #[test]
+  fn from_f64_test(alphabet in alphabet(), x in -1e9..1e9f64, y in prop_oneof![-1e9..1e9f64, -1.0..1.0f64]) {
+    let (a, b) = (alphabet.from_f64(x).unwrap(), alphabet.from_f64(y).unwrap());
+    prop_assert_eq!(alphabet.to_f64(&a).unwrap(), x);
+    prop_assert_eq!(alphabet.to_f64(&b).unwrap(), y);
+    prop_assert_eq!(x.partial_cmp(&y), Some(a.cmp(&b)), "{} is {:?} but {} is {:?}", x, a, y, b);
+  }
+}
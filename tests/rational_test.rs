@@ -0,0 +1,105 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{
+  float64_approx, from_f64, from_rational, n_keys_between, to_f64, to_rational, Alphabet, Error,
+};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+const SMALLEST: &str = "A00000000000000000000000000";
+
+fn ratio(numer: i64, denom: i64) -> BigRational {
+  BigRational::new(BigInt::from(numer), BigInt::from(denom))
+}
+
+#[test]
+fn rational_test() -> Result<(), Error> {
+  for (key, numer, denom) in [
+    ("a0", 0, 1),
+    ("a1", 1, 1),
+    ("az", 61, 1),
+    ("b00", 62, 1),
+    ("b10", 124, 1),
+    ("c000", 62 + 62 * 62, 1),
+    ("Zz", -1, 1),
+    ("Z0", -62, 1),
+    ("Yzz", -63, 1),
+    ("a0V", 1, 2),
+    ("a00V", 1, 124),
+    ("ZzV", -1, 2),
+    ("Z0V", -123, 2),
+  ] {
+    assert_eq!(to_rational(key)?, ratio(numer, denom), "{}", key);
+    assert_eq!(from_rational(&ratio(numer, denom))?, key);
+  }
+
+  // the largest and smallest keys
+  let largest = "zzzzzzzzzzzzzzzzzzzzzzzzzzz";
+  let limit = to_rational(largest)? + ratio(1, 1);
+  assert_eq!(from_rational(&(limit.clone() - ratio(1, 1)))?, largest);
+  assert_eq!(from_rational(&limit), Err(Error::RangeOverflow));
+  assert_eq!(from_rational(&-limit.clone()), Err(Error::RangeUnderflow));
+  assert_eq!(from_rational(&(ratio(1, 2) - limit))?, format!("{}V", SMALLEST));
+
+  assert_eq!(from_rational(&ratio(1, 3)), Err(Error::Unrepresentable));
+  assert_eq!(Alphabet::HEX.from_rational(&ratio(1, 3)), Err(Error::Unrepresentable));
+  assert_eq!(Alphabet::BASE36.from_rational(&ratio(1, 3))?, "i0c");
+  assert_eq!(to_rational(SMALLEST), Err(Error::SmallestKey { key: SMALLEST.into() }));
+  Ok(())
+}
+
+#[test]
+fn order_test() -> Result<(), Error> {
+  let keys = n_keys_between(&None, &None, 200)?;
+  let mut keys: Vec<String> = keys
+    .iter()
+    .flat_map(|key| [key.clone(), format!("{}V", key), format!("{}01", key)])
+    .collect();
+  keys.extend(["zzzzzzzzzzzzzzzzzzzzzzzzzzz".to_owned(), format!("{}V", SMALLEST)]);
+  keys.sort();
+
+  for pair in keys.windows(2) {
+    assert!(to_rational(&pair[0])? < to_rational(&pair[1])?, "{:?}", pair);
+    assert!(to_f64(&pair[0])? <= to_f64(&pair[1])?, "{:?}", pair);
+  }
+  for key in &keys {
+    assert_eq!(&from_rational(&to_rational(key)?)?, key);
+  }
+  Ok(())
+}
+
+#[test]
+fn f64_test() -> Result<(), Error> {
+  for (value, key) in [
+    (0.0, "a0"),
+    (-0.0, "a0"),
+    (1.0, "a1"),
+    (62.0, "b00"),
+    (0.5, "a0V"),
+    (-1.0, "Zz"),
+    (-0.5, "ZzV"),
+    (0.1, "a06COnbCOnbH"),
+    (-0.1, "ZztnbCOnbCOj"),
+    (1.0 / 3.0, "a0KfKfKfKfKQ"),
+  ] {
+    assert_eq!(from_f64(value)?, key, "{}", value);
+    assert_eq!(to_f64(key)?, value, "{}", key);
+  }
+
+  // exact where float64_approx isn't
+  let (a, b) = ("z10000000000000000000000000", "z10000000000000000000000001");
+  assert_eq!(float64_approx(a)?, float64_approx(b)?);
+  assert!(to_rational(a)? < to_rational(b)?);
+
+  for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+    assert_eq!(from_f64(value), Err(Error::Unrepresentable));
+  }
+  assert_eq!(from_f64(1e50), Err(Error::RangeOverflow));
+  assert_eq!(from_f64(-1e50), Err(Error::RangeUnderflow));
+
+  // odd radix, 0.5 has no finite expansion but the key rounds back to it
+  let key = Alphabet::BASE95.from_f64(0.5)?;
+  assert_eq!(key, "a OOOOOOOOP");
+  assert_eq!(Alphabet::BASE95.to_f64(&key)?, 0.5);
+  Ok(())
+}
@@ -0,0 +1,86 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{key_between, rebalance, rebalance_window, Alphabet, Error, FractionalIndex};
+
+/// keys of examples/debug.rs: 1000 insertions at the front of Xb0M0V, sorted
+fn long_keys() -> Result<Vec<String>, Error> {
+  let left = Some("Xb0M".to_owned());
+  let mut right = "Xb0M0V".to_owned();
+  let mut keys = vec![left.to_owned().unwrap(), right.to_owned()];
+  for _ in 0..1000 {
+    right = key_between(&left, &Some(right))?;
+    keys.push(right.to_owned());
+  }
+  keys.sort();
+  Ok(keys)
+}
+
+fn check_sorted(keys: &[String]) -> Result<(), Error> {
+  for key in keys {
+    FractionalIndex::parse(key)?;
+  }
+  for pair in keys.windows(2) {
+    assert!(pair[0] < pair[1], "{} >= {}", pair[0], pair[1]);
+  }
+  Ok(())
+}
+
+#[test]
+fn rebalance_test() -> Result<(), Error> {
+  let keys = long_keys()?;
+  assert!(keys.iter().map(String::len).max().unwrap() > 150);
+
+  let balanced = rebalance(&keys)?;
+  assert_eq!(balanced.len(), keys.len());
+  check_sorted(&balanced)?;
+  assert!(balanced.iter().all(|key| key.len() <= 4));
+
+  assert_eq!(rebalance::<&str>(&[])?, Vec::<String>::new());
+  assert_eq!(rebalance(&["b125", "b129"])?, vec!["aK", "af"]);
+  let indexes = vec![FractionalIndex::parse("a0")?, FractionalIndex::parse("a0V")?];
+  assert_eq!(rebalance(&indexes)?, vec!["aK", "af"]);
+  assert_eq!(Alphabet::HEX.rebalance(&["80", "8f"])?, vec!["84", "8a"]);
+
+  assert_eq!(
+    rebalance(&["a1", "a0"]),
+    Err(Error::InvalidOrder {
+      a: "a1".into(),
+      b: "a0".into()
+    })
+  );
+  assert_eq!(
+    rebalance(&["a0", "a00"]),
+    Err(Error::TrailingZero { key: "a00".into() })
+  );
+  Ok(())
+}
+
+#[test]
+fn rebalance_window_test() -> Result<(), Error> {
+  let mut keys = long_keys()?;
+  let (range, window) = rebalance_window(&keys, 10)?;
+  assert_eq!(range.len(), window.len());
+  // the short keys at the end are kept
+  assert!(range.end < keys.len());
+  keys.splice(range, window);
+  check_sorted(&keys)?;
+  assert!(keys.iter().all(|key| key.len() <= 10));
+  assert_eq!(rebalance_window(&keys, 10)?, (0..0, vec![]));
+
+  // a single long key only moves itself
+  let keys = ["a0", "a0V", "a0VVVVVVVVVVV", "a1"];
+  assert_eq!(rebalance_window(&keys, 4)?, (2..3, vec!["a0k".to_owned()]));
+
+  // neighbours too close to each other grow the window
+  let keys = ["a0", "a0V", "a0V1", "a0V11111111", "a0V2", "a1"];
+  let (range, window) = rebalance_window(&keys, 4)?;
+  assert_eq!(range, 2..5);
+  assert_eq!(window, vec!["a0c", "a0k", "a0s"]);
+
+  // no window fits, every key is rewritten
+  let keys = ["a0", "a0V", "a0VVVVV", "a1"];
+  let (range, window) = rebalance_window(&keys, 1)?;
+  assert_eq!(range, 0..4);
+  assert_eq!(window, rebalance(&keys)?);
+  Ok(())
+}
@@ -0,0 +1,87 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{key_for_move, keys_for_block_move, n_keys_between, Error};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn key_for_move_test() -> Result<(), Error> {
+  let keys = ["a0", "a1", "a2", "a3"];
+  // moving down lands after the item at to, moving up before it
+  assert_eq!(key_for_move(&keys, 0, 2)?, "a2V");
+  assert_eq!(key_for_move(&keys, 3, 1)?, "a0V");
+  assert_eq!(key_for_move(&keys, 1, 0)?, "Zz");
+  assert_eq!(key_for_move(&keys, 0, 3)?, "a4");
+  assert_eq!(key_for_move(&keys, 2, 2)?, "a2");
+
+  assert_eq!(keys_for_block_move(&keys, 0..2, 2)?, vec!["a4", "a5"]);
+  assert_eq!(keys_for_block_move(&keys, 2..4, 0)?, vec!["Zy", "Zz"]);
+  assert_eq!(keys_for_block_move(&keys, 1..3, 1)?, vec!["a1", "a2"]);
+  assert_eq!(keys_for_block_move(&keys, 3..4, 1)?, vec!["a0V"]);
+  assert_eq!(keys_for_block_move(&keys, 1..1, 3)?, Vec::<String>::new());
+
+  // only the neighbours are checked
+  assert_eq!(
+    key_for_move(&["a0", "a2", "a1"], 0, 1),
+    Err(Error::InvalidOrder {
+      a: "a2".into(),
+      b: "a1".into()
+    })
+  );
+  assert_eq!(
+    key_for_move(&["a0", "a1", "a20"], 0, 1),
+    Err(Error::TrailingZero { key: "a20".into() })
+  );
+  Ok(())
+}
+
+#[test]
+#[should_panic]
+fn key_for_move_out_of_bounds_test() {
+  key_for_move(&["a0", "a1"], 0, 2).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn block_move_out_of_bounds_test() {
+  keys_for_block_move(&["a0", "a1", "a2"], 0..2, 2).unwrap();
+}
+
+/// items paired with their keys, sorted by key
+fn sorted(mut items: Vec<(String, usize)>) -> Vec<(String, usize)> {
+  items.sort();
+  items
+}
+
+#[test]
+fn move_model_test() -> Result<(), Error> {
+  let mut rng = StdRng::seed_from_u64(3);
+  let keys = n_keys_between(&None, &None, 30)?;
+  let mut items: Vec<(String, usize)> = keys.into_iter().zip(0..).collect();
+  let mut model: Vec<usize> = (0..30).collect();
+
+  for _ in 0..1000 {
+    let keys: Vec<&str> = items.iter().map(|(key, _)| key.as_str()).collect();
+    if rng.gen() {
+      let (from, to) = (rng.gen_range(0..30), rng.gen_range(0..30));
+      let key = key_for_move(&keys, from, to)?;
+      items[from].0 = key;
+      let item = model.remove(from);
+      model.insert(to, item);
+    } else {
+      let len = rng.gen_range(0..10);
+      let start = rng.gen_range(0..=30 - len);
+      let to = rng.gen_range(0..=30 - len);
+      let new_keys = keys_for_block_move(&keys, start..start + len, to)?;
+      for (item, key) in items[start..start + len].iter_mut().zip(new_keys) {
+        item.0 = key;
+      }
+      let block: Vec<usize> = model.drain(start..start + len).collect();
+      model.splice(to..to, block);
+    }
+    items = sorted(items);
+    let order: Vec<usize> = items.iter().map(|(_, item)| *item).collect();
+    assert_eq!(order, model);
+  }
+  Ok(())
+}
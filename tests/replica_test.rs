@@ -0,0 +1,95 @@
+extern crate lexicon_fractional_index;
+
+use std::collections::HashSet;
+
+use lexicon_fractional_index::{key_between, key_between_with_replica, replica_id, Alphabet, Error, FractionalIndex};
+
+#[test]
+fn replica_key_test() -> Result<(), Error> {
+  fn test_check(a: Option<&str>, b: Option<&str>, replica: u64, exp: &str) -> Result<(), Error> {
+    let act = key_between_with_replica(&a.map(ToOwned::to_owned), &b.map(ToOwned::to_owned), replica)?;
+    assert_eq!(exp, act);
+    assert_eq!(replica, replica_id(&act)?);
+    Ok(())
+  }
+
+  test_check(None, None, 0, "a001")?;
+  test_check(None, None, 7, "a071")?;
+  test_check(None, None, 62, "a0102")?;
+  test_check(Some("a0"), Some("a1"), 1, "a0V11")?;
+  test_check(Some("a0"), None, 3, "a131")?;
+  // "a0" is a prefix of "a0V"
+  test_check(None, Some("a0V"), 3, "Zz31")?;
+  test_check(Some("Zz"), Some("a0V"), 3, "ZzV31")?;
+  test_check(None, None, u64::MAX, "a0LygHa16AHYFB")?;
+
+  Ok(())
+}
+
+#[test]
+fn concurrent_replicas_test() -> Result<(), Error> {
+  let bounds = [
+    (None, None),
+    (Some("a0".to_owned()), Some("a1".to_owned())),
+    (Some("a0".to_owned()), Some("a0001".to_owned())),
+    (None, Some("a0V".to_owned())),
+    (Some("a0".to_owned()), Some("a0V".to_owned())),
+  ];
+  for (a, b) in bounds.iter() {
+    let mut keys = HashSet::new();
+    for replica in (0..1000).chain(u64::MAX - 100..=u64::MAX) {
+      let key = key_between_with_replica(a, b, replica)?;
+      FractionalIndex::parse(&key)?;
+      if let Some(a) = a {
+        assert!(a < &key, "{} >= {}", a, key);
+      }
+      if let Some(b) = b {
+        assert!(&key < b, "{} >= {}", key, b);
+      }
+      assert_eq!(replica_id(&key)?, replica);
+      assert!(keys.insert(key));
+    }
+  }
+
+  // replica keys work as bounds of further inserts
+  let mut left = Some("a0".to_owned());
+  let right = Some("a1".to_owned());
+  for replica in 0..500 {
+    let key = key_between_with_replica(&left, &right, replica)?;
+    assert!(left.as_deref() < Some(key.as_str()) && Some(key.as_str()) < right.as_deref());
+    assert_eq!(replica_id(&key)?, replica);
+    left = Some(key);
+  }
+
+  Ok(())
+}
+
+#[test]
+fn replica_errors_test() -> Result<(), Error> {
+  assert_eq!(replica_id("a0"), Err(Error::MissingReplica { key: "a0".into() }));
+  assert_eq!(replica_id("a0V"), Err(Error::MissingReplica { key: "a0V".into() }));
+  assert_eq!(replica_id("a0z"), Err(Error::MissingReplica { key: "a0z".into() }));
+  assert_eq!(replica_id("a00"), Err(Error::TrailingZero { key: "a00".into() }));
+  // not generated with a replica, but decodable
+  assert_eq!(
+    replica_id(&key_between(&None, &Some("a0".to_owned()))?.replace("Zz", "Zz11"))?,
+    1
+  );
+
+  assert_eq!(
+    Alphabet::HEX.replica_id(&Alphabet::HEX.key_between_with_replica(&None, &None, 1 << 59)?)?,
+    1 << 59
+  );
+  assert_eq!(
+    Alphabet::HEX.key_between_with_replica(&None, &None, 1 << 60),
+    Err(Error::ReplicaTooLarge { replica: 1 << 60 })
+  );
+  let binary = Alphabet::new("01", "Aa")?;
+  assert_eq!(binary.key_between_with_replica(&None, &None, 1)?, "a011");
+  assert_eq!(
+    binary.key_between_with_replica(&None, &None, 2),
+    Err(Error::ReplicaTooLarge { replica: 2 })
+  );
+
+  Ok(())
+}
@@ -0,0 +1,53 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{Error, FractionalIndex};
+use rusqlite::Connection;
+
+fn items() -> rusqlite::Result<Connection> {
+  let conn = Connection::open_in_memory()?;
+  conn.execute("CREATE TABLE items (key TEXT NOT NULL)", ())?;
+  Ok(conn)
+}
+
+#[test]
+fn round_trip_test() -> Result<(), Box<dyn std::error::Error>> {
+  let conn = items()?;
+  let a = FractionalIndex::default();
+  let b = a.after()?;
+  let c = a.between(&b)?;
+  for key in [&b, &c, &a] {
+    conn.execute("INSERT INTO items (key) VALUES (?1)", [key])?;
+  }
+
+  let mut select = conn.prepare("SELECT key FROM items ORDER BY key")?;
+  let keys = select
+    .query_map((), |row| row.get(0))?
+    .collect::<Result<Vec<FractionalIndex>, _>>()?;
+  assert_eq!(keys, [a, c, b]);
+
+  Ok(())
+}
+
+#[test]
+fn invalid_key_test() -> rusqlite::Result<()> {
+  let conn = items()?;
+  conn.execute("INSERT INTO items (key) VALUES ('a00')", ())?;
+  let err = conn
+    .query_row("SELECT key FROM items", (), |row| row.get::<_, FractionalIndex>(0))
+    .unwrap_err();
+  match err {
+    rusqlite::Error::FromSqlConversionFailure(0, _, e) => assert_eq!(
+      e.downcast_ref::<Error>(),
+      Some(&Error::TrailingZero { key: "a00".into() })
+    ),
+    e => panic!("unexpected error: {}", e),
+  }
+
+  // not TEXT
+  let err = conn
+    .query_row("SELECT 1", (), |row| row.get::<_, FractionalIndex>(0))
+    .unwrap_err();
+  assert!(matches!(err, rusqlite::Error::InvalidColumnType(..)), "{}", err);
+
+  Ok(())
+}
@@ -0,0 +1,60 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{Error, FractionalIndex};
+
+#[test]
+fn round_trip_test() -> Result<(), Error> {
+  let key = FractionalIndex::parse("a0V")?;
+  let json = serde_json::to_string(&key).unwrap();
+  assert_eq!(json, "\"a0V\"");
+  assert_eq!(serde_json::from_str::<FractionalIndex>(&json).unwrap(), key);
+
+  let keys: Vec<FractionalIndex> = serde_json::from_str(r#"["Zz","a0","a1"]"#).unwrap();
+  let strings: Vec<&str> = keys.iter().map(FractionalIndex::as_str).collect();
+  assert_eq!(strings, ["Zz", "a0", "a1"]);
+
+  Ok(())
+}
+
+#[test]
+fn invalid_key_test() {
+  for (json, message) in [
+    (r#""a00""#, "invalid order key: a00"),
+    (r#""""#, "invalid order key"),
+    (r#""0""#, "invalid order key head: 0"),
+    (
+      r#""A00000000000000000000000000""#,
+      "invalid order key: A00000000000000000000000000",
+    ),
+  ] {
+    let err = serde_json::from_str::<FractionalIndex>(json).unwrap_err();
+    assert!(err.to_string().starts_with(message), "{}: {}", json, err);
+  }
+
+  assert!(serde_json::from_str::<FractionalIndex>("1").is_err());
+}
+
+#[cfg(feature = "crdt")]
+#[test]
+fn crdt_op_test() -> Result<(), Error> {
+  use lexicon_fractional_index::crdt::{Op, Sequence};
+
+  let mut a = Sequence::new(1);
+  let mut b = Sequence::new(2);
+  let mut ops = vec![a.insert(0, "x".to_owned())?, a.insert(1, "y".to_owned())?];
+  ops.push(a.move_to(1, 0)?);
+  ops.push(a.delete(1));
+
+  let json = serde_json::to_string(&ops).unwrap();
+  for op in serde_json::from_str::<Vec<Op<String>>>(&json).unwrap() {
+    b.apply(op);
+  }
+  assert_eq!(b.iter().collect::<Vec<_>>(), a.iter().collect::<Vec<_>>());
+  assert_eq!(b.iter().collect::<Vec<_>>(), ["y"]);
+
+  // keys of ops are validated too
+  let json = json.replacen("\"key\":\"", "\"key\":\"0", 1);
+  assert!(serde_json::from_str::<Vec<Op<String>>>(&json).is_err());
+
+  Ok(())
+}
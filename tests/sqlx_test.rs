@@ -0,0 +1,54 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{Error, FractionalIndex};
+use sqlx::{Connection, SqliteConnection};
+
+async fn items() -> sqlx::Result<SqliteConnection> {
+  let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+  sqlx::query("CREATE TABLE items (key TEXT NOT NULL)")
+    .execute(&mut conn)
+    .await?;
+  Ok(conn)
+}
+
+#[tokio::test]
+async fn round_trip_test() -> Result<(), Box<dyn std::error::Error>> {
+  let mut conn = items().await?;
+  let a = FractionalIndex::default();
+  let b = a.after()?;
+  let c = a.between(&b)?;
+  for key in [&b, &c, &a] {
+    sqlx::query("INSERT INTO items (key) VALUES (?1)")
+      .bind(key.to_owned())
+      .execute(&mut conn)
+      .await?;
+  }
+
+  let keys: Vec<FractionalIndex> = sqlx::query_scalar("SELECT key FROM items ORDER BY key")
+    .fetch_all(&mut conn)
+    .await?;
+  assert_eq!(keys, [a, c, b]);
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn invalid_key_test() -> sqlx::Result<()> {
+  let mut conn = items().await?;
+  sqlx::query("INSERT INTO items (key) VALUES ('a00')")
+    .execute(&mut conn)
+    .await?;
+  let err = sqlx::query_scalar::<_, FractionalIndex>("SELECT key FROM items")
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+  match err {
+    sqlx::Error::ColumnDecode { source, .. } => assert_eq!(
+      source.downcast_ref::<Error>(),
+      Some(&Error::TrailingZero { key: "a00".into() })
+    ),
+    e => panic!("unexpected error: {}", e),
+  }
+
+  Ok(())
+}
@@ -0,0 +1,51 @@
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::{
+  key_between, key_between_unchecked, n_keys_between, n_keys_between_unchecked, Alphabet, Error, FractionalIndex,
+};
+
+#[test]
+fn same_keys_test() -> Result<(), Error> {
+  let keys = n_keys_between(&None, &None, 20)?;
+  let mut bounds: Vec<Option<&str>> = keys.iter().map(|key| Some(key.as_str())).collect();
+  bounds.insert(0, None);
+  bounds.push(None);
+
+  for pair in bounds.windows(2) {
+    let (a, b) = (pair[0], pair[1]);
+    let (owned_a, owned_b) = (a.map(ToOwned::to_owned), b.map(ToOwned::to_owned));
+    assert_eq!(key_between_unchecked(a, b)?, key_between(&owned_a, &owned_b)?);
+    assert_eq!(
+      n_keys_between_unchecked(a, b, 5)?,
+      n_keys_between(&owned_a, &owned_b, 5)?
+    );
+  }
+
+  assert_eq!(Alphabet::HEX.key_between_unchecked(Some("80"), Some("81"))?, "808");
+
+  let key = FractionalIndex::new_unchecked("a0V".to_owned());
+  assert_eq!(key, FractionalIndex::parse("a0V")?);
+  assert_eq!(key.after()?.as_str(), "a1");
+  Ok(())
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unchecked bounds")]
+fn invalid_bounds_test() {
+  let _ = key_between_unchecked(Some("a0é"), None);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unchecked bounds")]
+fn unordered_bounds_test() {
+  let _ = n_keys_between_unchecked(Some("a1"), Some("a0"), 2);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unchecked key")]
+fn invalid_key_test() {
+  let _ = FractionalIndex::new_unchecked("a00".to_owned());
+}
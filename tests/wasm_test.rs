@@ -0,0 +1,96 @@
+//! natively, only calls that succeed can run: errors are JavaScript values.
+//! Errors are tested on wasm32 with `wasm-pack test --node --features wasm-bindgen`.
+
+extern crate lexicon_fractional_index;
+
+use lexicon_fractional_index::wasm::{generate_key_between, generate_n_keys_between};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn some(key: &str) -> Option<String> {
+  Some(key.to_owned())
+}
+
+#[test]
+fn generate_key_between_test() {
+  // same cases as the fractional-indexing package
+  let cases = [
+    (None, None, "a0"),
+    (None, some("a0"), "Zz"),
+    (None, some("Zz"), "Zy"),
+    (some("a0"), None, "a1"),
+    (some("a1"), None, "a2"),
+    (some("a0"), some("a1"), "a0V"),
+    (some("a1"), some("a2"), "a1V"),
+    (some("a0V"), some("a1"), "a0l"),
+    (some("Zz"), some("a0"), "ZzV"),
+    (some("Zz"), some("a1"), "a0"),
+    (None, some("Y00"), "Xzzz"),
+    (some("bzz"), None, "c000"),
+    (some("a0"), some("a0V"), "a0G"),
+    (some("a0"), some("a0G"), "a08"),
+    (some("b125"), some("b129"), "b127"),
+    (some("a0"), some("a1V"), "a1"),
+    (some("Zz"), some("a01"), "a0"),
+    (None, some("a0V"), "a0"),
+    (None, some("b999"), "b99"),
+  ];
+  for (a, b, expected) in cases {
+    let key = generate_key_between(a.to_owned(), b.to_owned(), None).unwrap();
+    assert_eq!(key, expected, "{:?} {:?}", a, b);
+  }
+}
+
+#[test]
+fn generate_n_keys_between_test() {
+  let keys = |a: Option<String>, b: Option<String>, n| generate_n_keys_between(a, b, n, None).unwrap();
+  assert_eq!(keys(None, None, 5), ["a0", "a1", "a2", "a3", "a4"]);
+  assert_eq!(
+    keys(some("a4"), None, 10),
+    ["a5", "a6", "a7", "a8", "a9", "aA", "aB", "aC", "aD", "aE"]
+  );
+  assert_eq!(keys(None, some("a0"), 5), ["Zv", "Zw", "Zx", "Zy", "Zz"]);
+  assert_eq!(
+    keys(some("a0"), some("a2"), 20).join(" "),
+    "a04 a08 a0G a0K a0O a0V a0Z a0d a0l a0t a1 a14 a18 a1G a1O a1V a1Z a1d a1l a1t"
+  );
+  assert!(keys(some("a0"), some("a1"), 0).is_empty());
+}
+
+#[test]
+fn digits_test() {
+  let base10 = || Some("0123456789".to_owned());
+  assert_eq!(generate_key_between(None, None, base10()).unwrap(), "a0");
+  assert_eq!(generate_key_between(some("a0"), some("a1"), base10()).unwrap(), "a05");
+  assert_eq!(generate_key_between(some("a9"), None, base10()).unwrap(), "b00");
+  assert_eq!(
+    generate_n_keys_between(some("a0"), some("a1"), 3, base10()).unwrap(),
+    ["a03", "a05", "a08"]
+  );
+
+  // every call builds its own alphabet, nothing is kept per digits string
+  for digits in ["01234567", "012345678", "0123456789"] {
+    let next = generate_key_between(some("a0"), None, Some(digits.to_owned())).unwrap();
+    assert_eq!(next, "a1");
+  }
+  let octal = Some("01234567".to_owned());
+  assert_eq!(generate_key_between(some("a7"), None, octal).unwrap(), "b00");
+
+  let base62 = Some("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz".to_owned());
+  assert_eq!(generate_key_between(some("a0"), some("a1"), base62).unwrap(), "a0V");
+}
+
+#[cfg(target_arch = "wasm32")]
+#[test]
+fn error_test() {
+  use wasm_bindgen::JsValue;
+
+  let message = |e: wasm_bindgen::JsError| String::from(js_sys::Error::from(JsValue::from(e)).message());
+  let err = generate_key_between(some("a00"), None, None).unwrap_err();
+  assert_eq!(message(err), "invalid order key: a00");
+  let err = generate_key_between(some("a1"), some("a0"), None).unwrap_err();
+  assert_eq!(message(err), "invalid order: a1 >= a0");
+  let err = generate_key_between(None, None, Some("10".to_owned())).unwrap_err();
+  assert_eq!(message(err), "invalid alphabet: 10");
+}